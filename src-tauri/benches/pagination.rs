@@ -0,0 +1,169 @@
+//! Compares keyset paging with the old `LIMIT/OFFSET` listing on a generated
+//! 100k note database. Run with `cargo bench --bench pagination`.
+
+use std::time::{Duration, Instant};
+
+use chrono::{Duration as Span, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use tauri_app_lib::db;
+use tauri_app_lib::notes::{self, Cursor, PageDirection};
+
+const NOTES: i64 = 100_000;
+const PAGE: u32 = 50;
+const RUNS: u32 = 20;
+
+async fn generate(pool: &SqlitePool) {
+    for migration in db::migrations() {
+        sqlx::raw_sql(migration.sql).execute(pool).await.unwrap();
+    }
+    let start = Utc::now() - Span::minutes(NOTES * 5);
+    let mut tx = pool.begin().await.unwrap();
+    for i in 0..NOTES {
+        let created_at = notes::timestamp(&(start + Span::minutes(i * 5)));
+        sqlx::query(
+            "INSERT INTO notes (content, created_at, updated_at, is_quick_capture)
+             VALUES (?1, ?2, ?2, ?3)",
+        )
+        .bind(format!("Generated note {}", i))
+        .bind(created_at)
+        .bind(i % 3 == 0)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        if i % 4 == 0 {
+            sqlx::query("INSERT INTO note_tags (note_id, tag) VALUES (last_insert_rowid(), ?1)")
+                .bind(format!("tag{}", i % 20))
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+        }
+    }
+    tx.commit().await.unwrap();
+    sqlx::query("ANALYZE").execute(pool).await.unwrap();
+}
+
+async fn time<F, Fut>(label: &str, mut run: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    run().await;
+    let started = Instant::now();
+    for _ in 0..RUNS {
+        run().await;
+    }
+    let per_run = started.elapsed() / RUNS;
+    println!("{:<40} {:>10.3} ms", label, per_run.as_secs_f64() * 1000.0);
+}
+
+/// Cursor of the page `depth` pages down, found by walking the list.
+async fn cursor_at(pool: &SqlitePool, depth: u32) -> Cursor {
+    let mut cursor: Option<Cursor> = None;
+    for _ in 0..depth {
+        let page = notes::list_page(pool, cursor.as_ref(), PageDirection::Older, Some(PAGE))
+            .await
+            .unwrap();
+        cursor = page.older.as_deref().and_then(Cursor::decode);
+    }
+    cursor.unwrap()
+}
+
+async fn offset_page(pool: &SqlitePool, offset: i64) {
+    sqlx::query(&format!(
+        "SELECT {} FROM notes WHERE TRIM(content) != ''
+         ORDER BY created_at DESC LIMIT ?1 OFFSET ?2",
+        notes::NOTE_COLUMNS
+    ))
+    .bind(i64::from(PAGE))
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+    .unwrap();
+    sqlx::query("SELECT COUNT(*) FROM notes WHERE TRIM(content) != ''")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::main]
+async fn main() {
+    let path = std::env::temp_dir().join("daily-notes-pagination-bench.db");
+    let _ = std::fs::remove_file(&path);
+    let options = SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .unwrap();
+
+    let started = Instant::now();
+    generate(&pool).await;
+    println!(
+        "generated {} notes in {:.1} s\n",
+        NOTES,
+        started.elapsed().as_secs_f64()
+    );
+
+    let shallow = cursor_at(&pool, 1).await;
+    let deep = cursor_at(&pool, 1_900).await;
+
+    time("keyset, first page", || async {
+        notes::list_page(&pool, None, PageDirection::Older, Some(PAGE))
+            .await
+            .unwrap();
+    })
+    .await;
+    time("keyset, page 2", || async {
+        notes::list_page(&pool, Some(&shallow), PageDirection::Older, Some(PAGE))
+            .await
+            .unwrap();
+    })
+    .await;
+    time("keyset, page 1900", || async {
+        notes::list_page(&pool, Some(&deep), PageDirection::Older, Some(PAGE))
+            .await
+            .unwrap();
+    })
+    .await;
+    time("keyset, page 1900 backwards", || async {
+        notes::list_page(&pool, Some(&deep), PageDirection::Newer, Some(PAGE))
+            .await
+            .unwrap();
+    })
+    .await;
+    time("offset + count, page 2", || {
+        offset_page(&pool, i64::from(PAGE))
+    })
+    .await;
+    time("offset + count, page 1900", || {
+        offset_page(&pool, 1_900 * i64::from(PAGE))
+    })
+    .await;
+
+    let mut walked = 0;
+    let started = Instant::now();
+    let mut cursor: Option<Cursor> = None;
+    loop {
+        let page = notes::list_page(&pool, cursor.as_ref(), PageDirection::Older, Some(PAGE))
+            .await
+            .unwrap();
+        walked += page.notes.len();
+        match page.older.as_deref().and_then(Cursor::decode) {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    let elapsed: Duration = started.elapsed();
+    println!(
+        "\nwalked all {} notes in {:.1} ms",
+        walked,
+        elapsed.as_secs_f64() * 1000.0
+    );
+    assert_eq!(walked as i64, NOTES);
+
+    pool.close().await;
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,289 @@
+// Archiving takes old notes out of the main list, the day view, pinned notes
+// and open tasks. Archived notes are still found by search, summarized and
+// exported with their day, and can be restored at any time. Archived notes
+// lose their pin, a restored note is not pinned again.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::notes::{self, NoteEntry, NOTE_COLUMNS};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchivePolicy {
+    /// Notes older than this many days are archived, `None` never archives
+    pub after_days: Option<u32>,
+    /// Pinned and starred notes stay in the list
+    pub keep_pinned: bool,
+    /// Notes with at least one tag stay in the list
+    pub keep_tagged: bool,
+}
+
+impl Default for ArchivePolicy {
+    fn default() -> Self {
+        ArchivePolicy {
+            after_days: None,
+            keep_pinned: true,
+            keep_tagged: false,
+        }
+    }
+}
+
+impl ArchivePolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.after_days {
+            Some(days) if !(7..=3650).contains(&days) => {
+                Err(format!("Invalid archive age: {}", days))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Notes created before this are archived, `None` when archiving is off.
+    fn cutoff(&self, now: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.after_days
+            .map(|days| *now - Duration::days(i64::from(days)))
+    }
+
+    /// Condition on `notes` for what gets archived, `?1` is the cutoff.
+    /// Open tasks are never archived, they would drop out of the carry over.
+    fn condition(&self) -> String {
+        let mut condition = "archived_at IS NULL AND created_at < ?1 \
+                             AND task_state IS NOT 'open'"
+            .to_string();
+        if self.keep_pinned {
+            condition.push_str(" AND pin_order IS NULL AND NOT starred");
+        }
+        if self.keep_tagged {
+            condition
+                .push_str(" AND NOT EXISTS (SELECT 1 FROM note_tags WHERE note_id = notes.id)");
+        }
+        condition
+    }
+}
+
+/// How many notes `policy` would archive right now.
+pub async fn preview(
+    pool: &SqlitePool,
+    policy: &ArchivePolicy,
+    now: &DateTime<Utc>,
+) -> Result<usize, String> {
+    let Some(cutoff) = policy.cutoff(now) else {
+        return Ok(0);
+    };
+    let count: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM notes WHERE {}",
+        policy.condition()
+    ))
+    .bind(notes::timestamp(&cutoff))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to count old notes: {}", e))?;
+    Ok(usize::try_from(count).unwrap_or_default())
+}
+
+/// Archives the notes `policy` considers old. Returns them before and after.
+pub async fn archive_old(
+    pool: &SqlitePool,
+    policy: &ArchivePolicy,
+    now: &DateTime<Utc>,
+) -> Result<Vec<(NoteEntry, NoteEntry)>, String> {
+    let Some(cutoff) = policy.cutoff(now) else {
+        return Ok(Vec::new());
+    };
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let old = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE {}",
+        NOTE_COLUMNS,
+        policy.condition()
+    ))
+    .bind(notes::timestamp(&cutoff))
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch old notes: {}", e))?;
+
+    let now = notes::timestamp(now);
+    let mut changed = Vec::new();
+    for before in old {
+        let mut after = before.clone();
+        after.archived_at = Some(now.clone());
+        after.pin_order = None;
+        notes::restore(&mut tx, after.id, Some(&after)).await?;
+        changed.push((before, after));
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit archive: {}", e))?;
+    Ok(changed)
+}
+
+/// Archives or restores note `id`. Returns it before and after.
+pub async fn set_archived(
+    pool: &SqlitePool,
+    id: i64,
+    archived: bool,
+    now: &DateTime<Utc>,
+) -> Result<(NoteEntry, NoteEntry), String> {
+    let before = notes::get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())?;
+    // Archiving twice keeps the original date
+    let archived_at = match (archived, &before.archived_at) {
+        (true, Some(at)) => Some(at.clone()),
+        (true, None) => Some(notes::timestamp(now)),
+        (false, _) => None,
+    };
+    let result = sqlx::query(
+        "UPDATE notes SET archived_at = ?1,
+             pin_order = CASE WHEN ?1 IS NULL THEN pin_order ELSE NULL END
+         WHERE id = ?2",
+    )
+    .bind(&archived_at)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to archive note: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err("Note not found".to_string());
+    }
+    let mut after = before.clone();
+    if archived_at.is_some() {
+        after.pin_order = None;
+    }
+    after.archived_at = archived_at;
+    Ok((before, after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::TaskState;
+    use crate::{db, pins};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-19T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn archiving_is_off_by_default() {
+        let policy = ArchivePolicy::default();
+        assert_eq!(policy.cutoff(&now()), None);
+        assert!(policy.validate().is_ok());
+    }
+
+    #[test]
+    fn cutoff_is_the_configured_age() {
+        let policy = ArchivePolicy {
+            after_days: Some(365),
+            ..ArchivePolicy::default()
+        };
+        assert_eq!(policy.cutoff(&now()), Some(now() - Duration::days(365)));
+        for days in [0, 6, 3651] {
+            let policy = ArchivePolicy {
+                after_days: Some(days),
+                ..ArchivePolicy::default()
+            };
+            assert!(policy.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn exemptions_extend_the_condition() {
+        let all = ArchivePolicy {
+            after_days: Some(30),
+            keep_pinned: false,
+            keep_tagged: false,
+        };
+        assert!(!all.condition().contains("pin_order"));
+        assert!(!all.condition().contains("note_tags"));
+
+        let keep = ArchivePolicy {
+            keep_pinned: true,
+            keep_tagged: true,
+            ..all
+        };
+        assert!(keep
+            .condition()
+            .contains("pin_order IS NULL AND NOT starred"));
+        assert!(keep.condition().contains("note_tags"));
+    }
+
+    async fn insert(pool: &SqlitePool, content: &str) -> NoteEntry {
+        let created_at = now() - Duration::days(60);
+        notes::insert_note(pool, &notes::NewNote::text(content, created_at))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn pinned_tagged_and_open_notes_survive() {
+        let pool = db::test_pool().await;
+        let old = insert(&pool, "old").await;
+        let pinned = insert(&pool, "pinned").await;
+        pins::toggle_pin(&pool, pinned.id).await.unwrap();
+        let starred = insert(&pool, "starred").await;
+        pins::toggle_star(&pool, starred.id).await.unwrap();
+        let mut tagged = notes::NewNote::text("tagged", now() - Duration::days(60));
+        tagged.tags = vec!["work".to_string()];
+        let tagged = notes::insert_note(&pool, &tagged).await.unwrap();
+        let mut task = notes::NewNote::text("task", now() - Duration::days(60));
+        task.task_state = Some(TaskState::Open);
+        let task = notes::insert_note(&pool, &task).await.unwrap();
+        let recent = notes::insert_note(&pool, &notes::NewNote::text("recent", now()))
+            .await
+            .unwrap();
+
+        let policy = ArchivePolicy {
+            after_days: Some(30),
+            keep_pinned: true,
+            keep_tagged: true,
+        };
+        assert_eq!(preview(&pool, &policy, &now()).await.unwrap(), 1);
+        let archived = archive_old(&pool, &policy, &now()).await.unwrap();
+        let ids: Vec<i64> = archived.iter().map(|(before, _)| before.id).collect();
+        assert_eq!(ids, vec![old.id]);
+        for id in [pinned.id, starred.id, tagged.id, task.id, recent.id] {
+            let note = notes::get_note(&pool, id).await.unwrap().unwrap();
+            assert_eq!(note.archived_at, None, "{}", note.content);
+        }
+    }
+
+    #[tokio::test]
+    async fn archived_notes_lose_their_pin() {
+        let pool = db::test_pool().await;
+        let pinned = insert(&pool, "pinned").await;
+        pins::toggle_pin(&pool, pinned.id).await.unwrap();
+        let other = insert(&pool, "other").await;
+        pins::toggle_pin(&pool, other.id).await.unwrap();
+
+        let policy = ArchivePolicy {
+            after_days: Some(30),
+            keep_pinned: false,
+            keep_tagged: false,
+        };
+        let archived = archive_old(&pool, &policy, &now()).await.unwrap();
+        assert_eq!(archived.len(), 2);
+        for (before, after) in &archived {
+            assert!(before.pin_order.is_some());
+            assert_eq!(after.pin_order, None);
+            let stored = notes::get_note(&pool, after.id).await.unwrap();
+            assert_eq!(stored.as_ref(), Some(after));
+        }
+
+        // Restoring does not pin again, archiving by hand unpins as well
+        let (_, restored) = set_archived(&pool, pinned.id, false, &now()).await.unwrap();
+        assert_eq!(restored.pin_order, None);
+        let pinned = pins::toggle_pin(&pool, pinned.id).await.unwrap();
+        let (before, after) = set_archived(&pool, pinned.id, true, &now()).await.unwrap();
+        assert!(before.pin_order.is_some());
+        assert_eq!(after.pin_order, None);
+        let stored = notes::get_note(&pool, pinned.id).await.unwrap();
+        assert_eq!(stored, Some(after));
+    }
+}
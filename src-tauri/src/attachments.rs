@@ -0,0 +1,322 @@
+// Files attached to notes. Contents are stored once per SHA-256 hash below
+// the attachments directory, rows in `attachments` refer to them by hash.
+// Deleting a note drops its rows, the blobs go in the next garbage collection
+// unless the undo history can still bring the note back.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::notes;
+
+pub const MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+// Blobs this young may belong to an attachment whose row is not written yet
+const GC_GRACE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Attachment {
+    pub id: i64,
+    pub note_id: i64,
+    pub name: String,
+    pub hash: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+impl Attachment {
+    /// File name on export, unique even when two attachments share a name.
+    pub fn export_name(&self) -> String {
+        format!("{}-{}", self.id, self.name)
+    }
+}
+
+impl<'r> FromRow<'r, SqliteRow> for Attachment {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let size: i64 = row.try_get("size")?;
+        Ok(Attachment {
+            id: row.try_get("id")?,
+            note_id: row.try_get("note_id")?,
+            name: row.try_get("name")?,
+            hash: row.try_get("hash")?,
+            size: u64::try_from(size).unwrap_or_default(),
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+const COLUMNS: &str = "id, note_id, name, hash, size, created_at";
+
+pub fn blob_path(root: &Path, hash: &str) -> PathBuf {
+    root.join(hash.get(..2).unwrap_or("00")).join(hash)
+}
+
+fn hash_of(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The last path component of `name` without control characters, so it
+/// can be used as a file name on export.
+fn file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let clean: String = base.chars().filter(|c| !c.is_control()).collect();
+    match clean.trim() {
+        "" | "." | ".." => "attachment".to_string(),
+        clean => clean.to_string(),
+    }
+}
+
+fn check_size(size: u64) -> Result<(), String> {
+    if size == 0 {
+        return Err("Attachment is empty".to_string());
+    }
+    if size > MAX_SIZE {
+        return Err(format!(
+            "Attachments are limited to {} MB",
+            MAX_SIZE / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+/// Writes `bytes` to their blob unless it exists already. Returns the hash.
+fn store(root: &Path, bytes: &[u8]) -> Result<String, String> {
+    let hash = hash_of(bytes);
+    let path = blob_path(root, &hash);
+    if path.exists() {
+        return Ok(hash);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+    }
+    // Renamed into place so a crash never leaves a truncated blob behind
+    let partial = path.with_extension("partial");
+    fs::write(&partial, bytes).map_err(|e| format!("Failed to write attachment: {}", e))?;
+    fs::rename(&partial, &path).map_err(|e| format!("Failed to write attachment: {}", e))?;
+    Ok(hash)
+}
+
+/// Attaches `bytes` to note `note_id` under `name`.
+pub async fn add(
+    pool: &SqlitePool,
+    root: &Path,
+    note_id: i64,
+    name: &str,
+    bytes: &[u8],
+) -> Result<Attachment, String> {
+    check_size(bytes.len() as u64)?;
+    if notes::get_note(pool, note_id).await?.is_none() {
+        return Err("Note not found".to_string());
+    }
+    let hash = store(root, bytes)?;
+    let id = sqlx::query(
+        "INSERT INTO attachments (note_id, name, hash, size, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(note_id)
+    .bind(file_name(name))
+    .bind(&hash)
+    .bind(bytes.len() as i64)
+    .bind(notes::timestamp(&chrono::Utc::now()))
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to save attachment: {}", e))?
+    .last_insert_rowid();
+    get(pool, id)
+        .await?
+        .ok_or_else(|| "Attachment not found".to_string())
+}
+
+/// Attaches a copy of the file at `path`.
+pub async fn add_file(
+    pool: &SqlitePool,
+    root: &Path,
+    note_id: i64,
+    path: &Path,
+) -> Result<Attachment, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    // Checked before reading so huge files are never loaded
+    check_size(size)?;
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    add(pool, root, note_id, &name, &bytes).await
+}
+
+pub async fn get(pool: &SqlitePool, id: i64) -> Result<Option<Attachment>, String> {
+    sqlx::query_as::<_, Attachment>(&format!(
+        "SELECT {} FROM attachments WHERE id = ?1",
+        COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch attachment: {}", e))
+}
+
+/// Attachments of the given notes, in the order they were added.
+pub async fn for_notes(pool: &SqlitePool, note_ids: &[i64]) -> Result<Vec<Attachment>, String> {
+    let ids =
+        serde_json::to_string(note_ids).map_err(|e| format!("Failed to encode ids: {}", e))?;
+    sqlx::query_as::<_, Attachment>(&format!(
+        "SELECT {} FROM attachments
+         WHERE note_id IN (SELECT value FROM json_each(?1))
+         ORDER BY id",
+        COLUMNS
+    ))
+    .bind(ids)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch attachments: {}", e))
+}
+
+/// Attachments of note `id`, e.g. before it is deleted.
+pub async fn of_note(conn: &mut SqliteConnection, id: i64) -> Result<Vec<Attachment>, String> {
+    sqlx::query_as::<_, Attachment>(&format!(
+        "SELECT {} FROM attachments WHERE note_id = ?1 ORDER BY id",
+        COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to fetch attachments: {}", e))
+}
+
+/// Writes a deleted attachment back under its old id, or moves it back to
+/// its old note.
+pub async fn reinsert(conn: &mut SqliteConnection, attachment: &Attachment) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO attachments (id, note_id, name, hash, size, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (id) DO UPDATE SET note_id = excluded.note_id",
+    )
+    .bind(attachment.id)
+    .bind(attachment.note_id)
+    .bind(&attachment.name)
+    .bind(&attachment.hash)
+    .bind(attachment.size as i64)
+    .bind(&attachment.created_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to restore attachment: {}", e))?;
+    Ok(())
+}
+
+/// Moves every attachment of note `from` to note `to`, e.g. when merging.
+pub async fn reassign(conn: &mut SqliteConnection, from: i64, to: i64) -> Result<(), String> {
+    sqlx::query("UPDATE attachments SET note_id = ?1 WHERE note_id = ?2")
+        .bind(to)
+        .bind(from)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to move attachments: {}", e))?;
+    Ok(())
+}
+
+/// Deletes an attachment. Its blob is left to the garbage collection, a
+/// deleted note on the undo history may still refer to it.
+pub async fn delete(pool: &SqlitePool, id: i64) -> Result<Attachment, String> {
+    let attachment = get(pool, id)
+        .await?
+        .ok_or_else(|| "Attachment not found".to_string())?;
+    sqlx::query("DELETE FROM attachments WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to delete attachment: {}", e))?;
+    Ok(attachment)
+}
+
+/// Removes blobs no attachment refers to, e.g. after their note was deleted.
+/// `keep` are blobs of deleted attachments that undo may restore. Returns how
+/// many were removed.
+pub async fn collect_garbage(
+    pool: &SqlitePool,
+    root: &Path,
+    keep: &HashSet<String>,
+) -> Result<usize, String> {
+    let mut referenced: HashSet<String> =
+        sqlx::query_scalar("SELECT DISTINCT hash FROM attachments")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to fetch attachments: {}", e))?
+            .into_iter()
+            .collect();
+    referenced.extend(keep.iter().cloned());
+    let Ok(dirs) = fs::read_dir(root) else {
+        // Nothing attached yet
+        return Ok(0);
+    };
+    let cutoff = SystemTime::now() - GC_GRACE;
+    let mut removed = 0;
+    for dir in dirs.flatten() {
+        let Ok(blobs) = fs::read_dir(dir.path()) else {
+            continue;
+        };
+        for blob in blobs.flatten() {
+            let name = blob.file_name().to_string_lossy().into_owned();
+            let recent = blob
+                .metadata()
+                .and_then(|m| m.modified())
+                .map_or(true, |modified| modified > cutoff);
+            if referenced.contains(&name) || recent {
+                continue;
+            }
+            fs::remove_file(blob.path())
+                .map_err(|e| format!("Failed to delete attachment file: {}", e))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_reduced_to_a_file_name() {
+        assert_eq!(file_name("/Users/me/Desktop/crash.log"), "crash.log");
+        assert_eq!(file_name("C:\\logs\\deploy.txt"), "deploy.txt");
+        assert_eq!(file_name("Bildschirmfoto\n.png"), "Bildschirmfoto.png");
+        assert_eq!(file_name(".."), "attachment");
+        assert_eq!(file_name(""), "attachment");
+    }
+
+    #[test]
+    fn size_limits_are_enforced() {
+        assert!(check_size(1).is_ok());
+        assert!(check_size(MAX_SIZE).is_ok());
+        assert_eq!(
+            check_size(MAX_SIZE + 1),
+            Err("Attachments are limited to 10 MB".to_string())
+        );
+        assert!(check_size(0).is_err());
+    }
+
+    #[test]
+    fn blobs_are_addressed_by_content() {
+        let hash = hash_of(b"hello");
+        assert_eq!(
+            hash,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(
+            blob_path(Path::new("/data"), &hash),
+            Path::new("/data/2c").join(&hash)
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt;
+
+// The autostart plugin writes a LaunchAgent plist on macOS, an XDG autostart
+// `.desktop` entry on Linux and a `Run` registry value on Windows. That entry
+// is the only record of the setting, so changes the user makes in the system
+// settings are never out of sync with the app.
+
+pub fn is_registered(app: &AppHandle) -> Result<bool, String> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to query autostart state: {}", e))
+}
+
+#[tauri::command]
+pub fn get_launch_at_login(app: AppHandle) -> Result<bool, String> {
+    is_registered(&app)
+}
+
+#[tauri::command]
+pub fn set_launch_at_login(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| format!("Failed to update autostart entry: {}", e))?;
+
+    is_registered(&app)
+}
@@ -0,0 +1,147 @@
+//! Adds notes from a terminal or script, next to the running app.
+//!
+//! ```text
+//! daily add [--at <when>] <text>...
+//! daily when <text>...
+//! ```
+//!
+//! `--at` understands the same dates as quick capture, e.g. "gestern 16 Uhr",
+//! "last friday" or "17.10. 9:30". `when` only prints what a date resolves
+//! to. Notes go into the app's database, `DAILY_DB` points at another file.
+//! Days begin at the hour set in the app, like they do there.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use chrono::{DateTime, Local, Utc};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::SqlitePool;
+use tauri_app_lib::{days, db, natural_date, notes, tasks};
+
+const USAGE: &str = "Usage: daily add [--at <when>] <text>...\n       daily when <text>...";
+
+/// Must match `identifier` in tauri.conf.json.
+const IDENTIFIER: &str = "com.andre.daily.app";
+
+/// Must match `SETTINGS_FILE` in settings.rs.
+const SETTINGS_FILE: &str = "settings.json";
+
+/// The directory Tauri's `app_config_dir` resolves for `IDENTIFIER`. The SQL
+/// plugin keeps the database there and the app its settings, so this is
+/// where both are found, not in the app data directory.
+fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library/Application Support")
+    } else if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else {
+        match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+    Some(base.join(IDENTIFIER))
+}
+
+fn database_path() -> Result<PathBuf, String> {
+    if let Some(path) = env::var_os("DAILY_DB") {
+        return Ok(PathBuf::from(path));
+    }
+    let file = db::DB_URL.trim_start_matches("sqlite:");
+    config_dir()
+        .map(|dir| dir.join(file))
+        .ok_or_else(|| "Failed to resolve config directory".to_string())
+}
+
+/// Applies the app's day start hour, the default midnight when the app
+/// never saved its settings.
+fn load_day_start_hour() {
+    let hour = config_dir()
+        .and_then(|dir| fs::read_to_string(dir.join(SETTINGS_FILE)).ok())
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|settings| settings.get("day_start_hour")?.as_u64())
+        .and_then(|hour| u32::try_from(hour).ok())
+        .unwrap_or(0);
+    days::set_day_start_hour(hour);
+}
+
+fn local_time(dt: &DateTime<Utc>) -> String {
+    dt.with_timezone(&Local)
+        .format("%d.%m.%Y %H:%M")
+        .to_string()
+}
+
+async fn add(at: Option<&str>, text: &str) -> Result<notes::NoteEntry, String> {
+    let created_at = match at {
+        Some(input) => natural_date::resolve(input)?,
+        None => Utc::now(),
+    };
+    let (task_state, content) = tasks::from_markdown(text);
+    let content = content.trim();
+    if content.is_empty() {
+        return Err("Note is empty".to_string());
+    }
+
+    let path = database_path()?;
+    // The app creates and migrates the database, don't leave an empty one
+    if !path.exists() {
+        return Err(format!(
+            "No database at {}, start the app once first",
+            path.display()
+        ));
+    }
+    let pool = SqlitePool::connect_with(SqliteConnectOptions::new().filename(&path))
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    notes::insert_note(
+        &pool,
+        &notes::NewNote {
+            content: content.to_string(),
+            created_at,
+            is_quick_capture: false,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state,
+        },
+    )
+    .await
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args.split_first() {
+        Some((command, rest)) if command == "add" => {
+            let (at, words) = match rest {
+                [flag, when, words @ ..] if flag == "--at" => (Some(when.as_str()), words),
+                words => (None, words),
+            };
+            if words.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            let note = tauri::async_runtime::block_on(add(at, &words.join(" ")))?;
+            let created_at = DateTime::parse_from_rfc3339(&note.created_at)
+                .map(|dt| local_time(&dt.with_timezone(&Utc)))
+                .unwrap_or(note.created_at);
+            println!("Added note {} for {}", note.id, created_at);
+            Ok(())
+        }
+        Some((command, rest)) if command == "when" && !rest.is_empty() => {
+            let dt = natural_date::resolve(&rest.join(" "))?;
+            println!("{}", local_time(&dt));
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() {
+    load_day_start_hour();
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,518 @@
+// Changes to many notes at once: retagging, sections, moving to another day,
+// archiving, deleting and merging. Each runs in one transaction and returns the notes
+// before and after, which is what the undo history records. Links to deleted
+// or merged notes are rewritten along the way.
+
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::days;
+use crate::history::{Change, Dependents};
+use crate::links::{self, Link};
+use crate::notes::{self, NoteEntry, Section, NOTE_COLUMNS};
+
+/// Which notes to change. Every given criterion has to match, an empty
+/// filter is refused rather than touching every note.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NoteFilter {
+    pub ids: Option<Vec<i64>>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub tag: Option<String>,
+    pub section: Option<Section>,
+    pub quick_capture: Option<bool>,
+    /// Case-insensitive substring of the content
+    pub text: Option<String>,
+    /// Only archived or only active notes. Not a criterion on its own.
+    pub archived: Option<bool>,
+}
+
+impl NoteFilter {
+    fn is_empty(&self) -> bool {
+        self.ids.is_none()
+            && self.from.is_none()
+            && self.to.is_none()
+            && self.tag.is_none()
+            && self.section.is_none()
+            && self.quick_capture.is_none()
+            && self.text.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    Tag {
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    SetSection {
+        section: Option<Section>,
+    },
+    MoveToDay {
+        day: NaiveDate,
+    },
+    ShiftDays {
+        days: i64,
+    },
+    Archive,
+    Unarchive,
+    Delete,
+    /// Into the oldest selected note, the others are deleted
+    Merge,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BulkSummary {
+    pub matched: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub merged_into: Option<i64>,
+    /// Other notes whose links were pointed elsewhere
+    pub relinked: usize,
+}
+
+#[derive(Debug)]
+pub struct BulkResult {
+    pub summary: BulkSummary,
+    pub changes: Vec<Change>,
+}
+
+pub async fn apply(
+    pool: &SqlitePool,
+    filter: &NoteFilter,
+    action: &BulkAction,
+) -> Result<BulkResult, String> {
+    if filter.is_empty() {
+        return Err("Select notes by id or filter".to_string());
+    }
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let selected = select(&mut tx, filter).await?;
+    let now = notes::timestamp(&Utc::now());
+    let mut summary = BulkSummary {
+        matched: selected.len(),
+        ..BulkSummary::default()
+    };
+    let mut changes = Vec::new();
+
+    match action {
+        BulkAction::Delete => {
+            for note in &selected {
+                for (before, after) in notes::unlink(&mut tx, note, &now).await? {
+                    changes.push(Change::updated(&before, &after));
+                    summary.relinked += 1;
+                }
+                let dependents = Dependents::load(&mut tx, note.id).await?;
+                notes::restore(&mut tx, note.id, None).await?;
+                changes.push(Change::deleted(note, dependents));
+            }
+            summary.deleted = selected.len();
+        }
+        BulkAction::Merge => {
+            let (target, rest) = match selected.split_first() {
+                Some((target, rest)) if !rest.is_empty() => (target, rest),
+                _ => return Err("Select at least two notes to merge".to_string()),
+            };
+            let merged = merge(target, rest, &now);
+            notes::restore(&mut tx, target.id, Some(&merged)).await?;
+            changes.push(Change::updated(target, &merged));
+            for note in rest {
+                let to = Link::Note(target.id);
+                for (before, after) in links::retarget(&mut tx, note.id, &to, &now).await? {
+                    changes.push(Change::updated(&before, &after));
+                    summary.relinked += 1;
+                }
+                let dependents = Dependents::move_to(&mut tx, note.id, target.id).await?;
+                notes::restore(&mut tx, note.id, None).await?;
+                changes.push(Change::deleted(note, dependents));
+            }
+            summary.updated = 1;
+            summary.deleted = rest.len();
+            summary.merged_into = Some(target.id);
+        }
+        _ => {
+            for note in &selected {
+                if let Some(after) = edit(note, action, &now)? {
+                    notes::restore(&mut tx, note.id, Some(&after)).await?;
+                    changes.push(Change::updated(note, &after));
+                }
+            }
+            summary.updated = changes.len();
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit bulk change: {}", e))?;
+    Ok(BulkResult { summary, changes })
+}
+
+/// Selected notes, oldest first.
+async fn select(
+    conn: &mut SqliteConnection,
+    filter: &NoteFilter,
+) -> Result<Vec<NoteEntry>, String> {
+    let ids = filter
+        .ids
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to encode ids: {}", e))?;
+    let start = filter
+        .from
+        .map(|day| notes::timestamp(&days::start_of_day(day)));
+    let end = filter
+        .to
+        .map(|day| notes::timestamp(&days::days_range(day, day).1));
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE (?1 IS NULL OR id IN (SELECT value FROM json_each(?1)))
+           AND (?2 IS NULL OR created_at >= ?2)
+           AND (?3 IS NULL OR created_at < ?3)
+           AND (?4 IS NULL OR EXISTS
+                (SELECT 1 FROM note_tags WHERE note_id = notes.id AND tag = ?4))
+           AND (?5 IS NULL OR section = ?5)
+           AND (?6 IS NULL OR is_quick_capture = ?6)
+           AND (?7 IS NULL OR INSTR(LOWER(content), LOWER(?7)) > 0)
+           AND (?8 IS NULL OR (archived_at IS NOT NULL) = ?8)
+         ORDER BY created_at ASC, id ASC",
+        NOTE_COLUMNS
+    ))
+    .bind(ids)
+    .bind(start)
+    .bind(end)
+    .bind(filter.tag.as_deref().map(normalize_tag))
+    .bind(filter.section.map(Section::as_str))
+    .bind(filter.quick_capture)
+    .bind(filter.text.as_deref())
+    .bind(filter.archived)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to select notes: {}", e))
+}
+
+/// Tags as the capture parser stores them, without `#` and lowercased.
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// The note after `action`, `None` when nothing changes.
+fn edit(note: &NoteEntry, action: &BulkAction, now: &str) -> Result<Option<NoteEntry>, String> {
+    let mut after = note.clone();
+    match action {
+        BulkAction::Tag { add, remove } => {
+            let remove: Vec<String> = remove.iter().map(|t| normalize_tag(t)).collect();
+            after.tags.retain(|tag| !remove.contains(tag));
+            after.tags.extend(
+                add.iter()
+                    .map(|t| normalize_tag(t))
+                    .filter(|t| !t.is_empty()),
+            );
+            after.tags.sort();
+            after.tags.dedup();
+        }
+        BulkAction::SetSection { section } => after.section = *section,
+        BulkAction::MoveToDay { day } => {
+            after.created_at = moved(note, |_| Some(*day))?;
+        }
+        BulkAction::ShiftDays { days } => {
+            let shift = Duration::try_days(*days)
+                .ok_or_else(|| format!("Cannot move notes by {} days", days))?;
+            after.created_at = moved(note, |from| from.checked_add_signed(shift))?;
+        }
+        BulkAction::Archive => {
+            after.archived_at = note.archived_at.clone().or_else(|| Some(now.to_string()));
+            after.pin_order = None;
+        }
+        BulkAction::Unarchive => after.archived_at = None,
+        BulkAction::Delete | BulkAction::Merge => return Ok(None),
+    }
+    let edited = after.tags != note.tags
+        || after.section != note.section
+        || after.created_at != note.created_at;
+    // Archiving alone is not an edit and keeps `updated_at`
+    if edited {
+        after.updated_at = now.to_string();
+    } else if after.archived_at == note.archived_at && after.pin_order == note.pin_order {
+        return Ok(None);
+    }
+    Ok(Some(after))
+}
+
+fn moved(note: &NoteEntry, to: impl Fn(NaiveDate) -> Option<NaiveDate>) -> Result<String, String> {
+    days::local_day_of(&note.created_at)
+        .and_then(to)
+        .and_then(|day| days::same_time_on(&note.created_at, day))
+        .map(|at| notes::timestamp(&at))
+        .ok_or_else(|| format!("Note {} has an invalid date", note.id))
+}
+
+/// `target` with the content of `rest` appended line by line. Tags are
+/// combined, durations added up, and a missing section or priority is taken
+/// from the other notes.
+fn merge(target: &NoteEntry, rest: &[NoteEntry], now: &str) -> NoteEntry {
+    let mut merged = target.clone();
+    let all = || std::iter::once(target).chain(rest);
+    merged.content = all()
+        .map(|note| note.content.trim())
+        .filter(|content| !content.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    merged.tags = all().flat_map(|note| note.tags.iter().cloned()).collect();
+    merged.tags.sort();
+    merged.tags.dedup();
+    merged.section = all().find_map(|note| note.section);
+    merged.priority = all().find_map(|note| note.priority);
+    merged.duration_minutes = all()
+        .filter_map(|note| note.duration_minutes)
+        .reduce(|a, b| a.saturating_add(b));
+    merged.updated_at = now.to_string();
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attachments;
+    use crate::db;
+    use crate::history::{self, Direction, Operation};
+    use crate::notes::{NewNote, Priority};
+
+    const NOW: &str = "2026-10-19T12:00:00.000Z";
+
+    fn note(id: i64, content: &str, tags: &[&str]) -> NoteEntry {
+        NoteEntry {
+            id,
+            content: content.to_string(),
+            created_at: "2026-10-19T08:00:00.000Z".to_string(),
+            updated_at: "2026-10-19T08:00:00.000Z".to_string(),
+            is_quick_capture: true,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            task_state: None,
+            completed_at: None,
+            processed_at: None,
+            pin_order: None,
+            starred: false,
+            archived_at: None,
+        }
+    }
+
+    fn tag(add: &[&str], remove: &[&str]) -> BulkAction {
+        BulkAction::Tag {
+            add: add.iter().map(|t| t.to_string()).collect(),
+            remove: remove.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn retagging_normalizes_and_skips_unchanged_notes() {
+        let before = note(1, "Deploy", &["ci", "ops"]);
+        let after = edit(&before, &tag(&["#Infra", "ci"], &["OPS"]), NOW)
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.tags, vec!["ci", "infra"]);
+        assert_eq!(after.updated_at, NOW);
+
+        assert!(edit(&before, &tag(&["ci"], &["backend"]), NOW)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn section_changes_only_touch_other_sections() {
+        let before = note(1, "Waiting on review", &[]);
+        let action = BulkAction::SetSection {
+            section: Some(Section::Blocker),
+        };
+        let after = edit(&before, &action, NOW).unwrap().unwrap();
+        assert_eq!(after.section, Some(Section::Blocker));
+        assert!(edit(&after, &action, NOW).unwrap().is_none());
+    }
+
+    #[test]
+    fn archiving_keeps_the_edit_time() {
+        let before = note(1, "Old standup", &[]);
+        let archived = edit(&before, &BulkAction::Archive, NOW).unwrap().unwrap();
+        assert_eq!(archived.archived_at.as_deref(), Some(NOW));
+        assert_eq!(archived.updated_at, before.updated_at);
+        assert!(edit(&archived, &BulkAction::Archive, NOW)
+            .unwrap()
+            .is_none());
+
+        let restored = edit(&archived, &BulkAction::Unarchive, NOW)
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.archived_at, None);
+        assert!(edit(&before, &BulkAction::Unarchive, NOW)
+            .unwrap()
+            .is_none());
+
+        let pinned = NoteEntry {
+            pin_order: Some(0),
+            ..before
+        };
+        let archived = edit(&pinned, &BulkAction::Archive, NOW).unwrap().unwrap();
+        assert_eq!(archived.pin_order, None);
+    }
+
+    #[test]
+    fn invalid_dates_are_reported() {
+        let mut before = note(4, "Standup", &[]);
+        before.created_at = "yesterday".to_string();
+        let error = edit(&before, &BulkAction::ShiftDays { days: 1 }, NOW).unwrap_err();
+        assert_eq!(error, "Note 4 has an invalid date");
+
+        let far = BulkAction::ShiftDays { days: i64::MAX };
+        let error = edit(&note(5, "Standup", &[]), &far, NOW).unwrap_err();
+        assert_eq!(error, format!("Cannot move notes by {} days", i64::MAX));
+        let far = BulkAction::ShiftDays {
+            days: 1_000_000_000,
+        };
+        let error = edit(&note(6, "Standup", &[]), &far, NOW).unwrap_err();
+        assert_eq!(error, "Note 6 has an invalid date");
+    }
+
+    #[test]
+    fn merging_keeps_the_oldest_note_and_combines_the_rest() {
+        let mut target = note(1, "Fixed login", &["auth"]);
+        target.duration_minutes = Some(30);
+        let mut second = note(2, "  ", &["auth", "backend"]);
+        second.priority = Some(Priority::High);
+        let mut third = note(3, "Wrote tests", &[]);
+        third.duration_minutes = Some(15);
+        third.section = Some(Section::Todo);
+
+        let merged = merge(&target, &[second, third], NOW);
+        assert_eq!(merged.id, 1);
+        assert_eq!(merged.content, "Fixed login\nWrote tests");
+        assert_eq!(merged.tags, vec!["auth", "backend"]);
+        assert_eq!(merged.priority, Some(Priority::High));
+        assert_eq!(merged.section, Some(Section::Todo));
+        assert_eq!(merged.duration_minutes, Some(45));
+        assert_eq!(merged.created_at, target.created_at);
+    }
+
+    async fn insert(pool: &SqlitePool, content: &str) -> NoteEntry {
+        notes::insert_note(pool, &NewNote::text(content, Utc::now()))
+            .await
+            .unwrap()
+    }
+
+    fn ids(ids: &[i64]) -> NoteFilter {
+        NoteFilter {
+            ids: Some(ids.to_vec()),
+            ..NoteFilter::default()
+        }
+    }
+
+    async fn content(pool: &SqlitePool, id: i64) -> Option<String> {
+        notes::get_note(pool, id).await.unwrap().map(|n| n.content)
+    }
+
+    #[tokio::test]
+    async fn deleting_relinks_and_undoes_as_one_operation() {
+        let pool = db::test_pool().await;
+        let target = insert(&pool, "Release").await;
+        let linking = insert(&pool, &format!("See [[note:{}]]", target.id)).await;
+        let other = insert(&pool, &format!("Also [[note:{}]]", target.id)).await;
+
+        // `linking` is relinked first and then deleted itself
+        let result = apply(&pool, &ids(&[target.id, linking.id]), &BulkAction::Delete)
+            .await
+            .unwrap();
+        assert_eq!(result.summary.deleted, 2);
+        assert_eq!(result.summary.relinked, 2);
+        assert_eq!(content(&pool, target.id).await, None);
+        let relinked = content(&pool, other.id).await.unwrap();
+        assert!(relinked.starts_with("Also [[20"), "{}", relinked);
+
+        let mut operation = Operation::new(result.changes);
+        assert!(history::apply(&pool, &mut operation, Direction::Undo)
+            .await
+            .unwrap());
+        assert_eq!(content(&pool, target.id).await.unwrap(), "Release");
+        assert_eq!(content(&pool, linking.id).await, Some(linking.content));
+        assert_eq!(content(&pool, other.id).await, Some(other.content));
+    }
+
+    #[tokio::test]
+    async fn merging_deletes_the_rest() {
+        let pool = db::test_pool().await;
+        let first = insert(&pool, "Fixed login").await;
+        let second = insert(&pool, "Wrote tests").await;
+        let result = apply(&pool, &ids(&[first.id, second.id]), &BulkAction::Merge)
+            .await
+            .unwrap();
+        assert_eq!(result.summary.merged_into, Some(first.id));
+        assert_eq!(
+            content(&pool, first.id).await.unwrap(),
+            "Fixed login\nWrote tests"
+        );
+        assert_eq!(content(&pool, second.id).await, None);
+    }
+
+    async fn attached_to(pool: &SqlitePool, id: i64) -> Option<i64> {
+        let attachment = attachments::get(pool, id).await.unwrap();
+        attachment.map(|a| a.note_id)
+    }
+
+    #[tokio::test]
+    async fn undoing_a_merge_moves_attachments_back() {
+        let pool = db::test_pool().await;
+        let root = std::env::temp_dir().join(format!("daily-merge-{}", std::process::id()));
+        let first = insert(&pool, "Fixed login").await;
+        let second = insert(&pool, "Wrote tests").await;
+        let attachment = attachments::add(&pool, &root, second.id, "tests.log", b"ok")
+            .await
+            .unwrap();
+
+        let result = apply(&pool, &ids(&[first.id, second.id]), &BulkAction::Merge)
+            .await
+            .unwrap();
+        assert_eq!(attached_to(&pool, attachment.id).await, Some(first.id));
+
+        let mut operation = Operation::new(result.changes);
+        assert!(history::apply(&pool, &mut operation, Direction::Undo)
+            .await
+            .unwrap());
+        assert_eq!(attached_to(&pool, attachment.id).await, Some(second.id));
+        assert!(history::apply(&pool, &mut operation, Direction::Redo)
+            .await
+            .unwrap());
+        assert_eq!(attached_to(&pool, attachment.id).await, Some(first.id));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_failing_note_rolls_back_the_whole_change() {
+        let pool = db::test_pool().await;
+        let valid = insert(&pool, "Standup").await;
+        let broken = insert(&pool, "Imported").await;
+        sqlx::query("UPDATE notes SET created_at = 'yesterday' WHERE id = ?1")
+            .bind(broken.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // `valid` sorts first and is moved before `broken` fails
+        let error = apply(
+            &pool,
+            &ids(&[valid.id, broken.id]),
+            &BulkAction::ShiftDays { days: 1 },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error, format!("Note {} has an invalid date", broken.id));
+        let stored = notes::get_note(&pool, valid.id).await.unwrap().unwrap();
+        assert_eq!(stored.created_at, valid.created_at);
+    }
+}
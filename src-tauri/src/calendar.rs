@@ -0,0 +1,155 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Which days count as workdays, for reminders and streaks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkCalendar {
+    pub workdays: Vec<Weekday>,
+    /// Days off that would otherwise be workdays, e.g. public holidays
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> Self {
+        WorkCalendar {
+            workdays: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            holidays: Vec::new(),
+        }
+    }
+}
+
+impl WorkCalendar {
+    pub fn is_workday(&self, day: NaiveDate) -> bool {
+        self.workdays.contains(&day.weekday()) && !self.holidays.contains(&day)
+    }
+
+    /// Closest workday strictly before `day`, looking back at most a year.
+    pub fn previous_workday(&self, day: NaiveDate) -> Option<NaiveDate> {
+        day.iter_days()
+            .rev()
+            .skip(1)
+            .take(366)
+            .find(|d| self.is_workday(*d))
+    }
+}
+
+/// Sprint cadence for summaries, e.g. two weeks starting on Monday.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SprintConfig {
+    pub length_weeks: u32,
+    pub start_weekday: Weekday,
+    /// Start of any past sprint, aligns sprints longer than a week. Without
+    /// it the current sprint ends with the current week.
+    pub anchor: Option<NaiveDate>,
+}
+
+impl Default for SprintConfig {
+    fn default() -> Self {
+        SprintConfig {
+            length_weeks: 2,
+            start_weekday: Weekday::Mon,
+            anchor: None,
+        }
+    }
+}
+
+impl SprintConfig {
+    fn length_days(&self) -> i64 {
+        i64::from(self.length_weeks.max(1)) * 7
+    }
+
+    /// Most recent start weekday on or before `day`.
+    fn week_start(&self, day: NaiveDate) -> NaiveDate {
+        let back = (7 + day.weekday().num_days_from_monday()
+            - self.start_weekday.num_days_from_monday())
+            % 7;
+        day - Duration::days(i64::from(back))
+    }
+
+    /// First and last day of the sprint containing `day`.
+    pub fn sprint_of(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let length = self.length_days();
+        let start = match self.anchor {
+            Some(anchor) => {
+                let anchor = self.week_start(anchor);
+                day - Duration::days((day - anchor).num_days().rem_euclid(length))
+            }
+            None => self.week_start(day) - Duration::days(length - 7),
+        };
+        (start, start + Duration::days(length - 1))
+    }
+
+    /// Sprint `back` sprints before the one containing `day`.
+    pub fn sprint_before(&self, day: NaiveDate, back: u32) -> (NaiveDate, NaiveDate) {
+        self.sprint_of(day - Duration::days(self.length_days() * i64::from(back)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn weekends_and_holidays_are_not_workdays() {
+        let calendar = WorkCalendar {
+            holidays: vec![day(12, 25)],
+            ..WorkCalendar::default()
+        };
+        assert!(calendar.is_workday(day(10, 19)));
+        assert!(!calendar.is_workday(day(10, 18)));
+        assert!(!calendar.is_workday(day(12, 25)));
+    }
+
+    #[test]
+    fn previous_workday_skips_weekends_and_holidays() {
+        let calendar = WorkCalendar {
+            holidays: vec![day(10, 16)],
+            ..WorkCalendar::default()
+        };
+        // Monday -> Friday is a holiday -> Thursday
+        assert_eq!(calendar.previous_workday(day(10, 19)), Some(day(10, 15)));
+    }
+
+    #[test]
+    fn empty_calendar_has_no_workdays() {
+        let calendar = WorkCalendar {
+            workdays: Vec::new(),
+            holidays: Vec::new(),
+        };
+        assert_eq!(calendar.previous_workday(day(10, 19)), None);
+    }
+
+    #[test]
+    fn sprint_without_anchor_ends_with_the_current_week() {
+        let sprint = SprintConfig::default();
+        // Wednesday 21st -> Monday 12th to Sunday 25th
+        assert_eq!(sprint.sprint_of(day(10, 21)), (day(10, 12), day(10, 25)));
+    }
+
+    #[test]
+    fn anchored_sprints_keep_their_cadence() {
+        let sprint = SprintConfig {
+            length_weeks: 2,
+            start_weekday: Weekday::Wed,
+            anchor: Some(day(1, 14)),
+        };
+        assert_eq!(sprint.sprint_of(day(10, 19)), (day(10, 7), day(10, 20)));
+        assert_eq!(sprint.sprint_of(day(10, 21)), (day(10, 21), day(11, 3)));
+        assert_eq!(
+            sprint.sprint_before(day(10, 21), 1),
+            (day(10, 7), day(10, 20))
+        );
+    }
+}
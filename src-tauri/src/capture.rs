@@ -0,0 +1,405 @@
+// Quick capture syntax. Recognized tokens are removed from the text and
+// turned into structured fields, everything else is kept as typed:
+//
+//   /todo /blocker /done    section, only as the first word
+//   #tag                    tag, must start with a letter (#123 stays text)
+//   @yesterday @mon @gestern @2026-10-17 @gestern-16uhr @vor-2-stunden
+//                           when the note happened
+//   !high !medium !low      priority (also !hoch !mittel !niedrig)
+//   ~30m ~1h ~1h30m ~90min  time spent
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+
+use crate::natural_date::{self, When};
+use crate::notes::{Priority, Section};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedCapture {
+    pub content: String,
+    pub section: Option<Section>,
+    pub tags: Vec<String>,
+    pub date: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+    pub priority: Option<Priority>,
+    pub duration_minutes: Option<u32>,
+}
+
+fn parse_section(token: &str) -> Option<Section> {
+    match token.strip_prefix('/')?.to_lowercase().as_str() {
+        "todo" => Some(Section::Todo),
+        "blocker" => Some(Section::Blocker),
+        "done" => Some(Section::Done),
+        _ => None,
+    }
+}
+
+fn parse_tag(token: &str) -> Option<String> {
+    let tag = token.strip_prefix('#')?;
+    let first = tag.chars().next()?;
+    let valid = first.is_alphabetic()
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '/');
+    if valid {
+        Some(tag.to_lowercase())
+    } else {
+        None
+    }
+}
+
+/// `@yesterday`, `@mon`, `@2026-10-17`, and longer natural language with
+/// dashes or underscores instead of spaces, like `@gestern-16uhr`.
+fn parse_date(token: &str, now: NaiveDateTime) -> Option<When> {
+    let value = token.strip_prefix('@')?;
+    natural_date::parse(value, now)
+        .or_else(|| natural_date::parse(&value.replace(['-', '_'], " "), now))
+}
+
+fn parse_priority(token: &str) -> Option<Priority> {
+    match token.strip_prefix('!')?.to_lowercase().as_str() {
+        "high" | "hoch" => Some(Priority::High),
+        "medium" | "mittel" => Some(Priority::Medium),
+        "low" | "niedrig" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// Accepts `30m`, `30min`, `2h`, `1.5h` and combinations like `1h30m`.
+fn parse_duration(token: &str) -> Option<u32> {
+    let mut rest = token.strip_prefix('~')?.to_lowercase();
+    let mut minutes = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return None;
+        }
+        let number: f64 = rest[..number_len].replace(',', ".").parse().ok()?;
+        rest = rest[number_len..].to_string();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "h" | "std" => 60.0,
+            "m" | "min" => 1.0,
+            _ => return None,
+        };
+        rest = rest[unit_len..].to_string();
+        minutes += number * factor;
+    }
+    let minutes = minutes.round();
+    if minutes >= 1.0 && minutes <= u32::MAX as f64 {
+        Some(minutes as u32)
+    } else {
+        None
+    }
+}
+
+/// Splits on whitespace, keeping the byte range of every token.
+fn tokens(input: &str) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for (index, c) in input.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                result.push((s, index));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        result.push((s, input.len()));
+    }
+    result
+}
+
+/// Removes the given token ranges plus the spaces that separated them from
+/// their neighbours, leaving all other text byte for byte as it was.
+fn strip_ranges(input: &str, ranges: &[(usize, usize)]) -> String {
+    let is_space = |c: char| c == ' ' || c == '\t';
+    let mut content = String::with_capacity(input.len());
+    let mut cursor = 0;
+    for &(start, end) in ranges {
+        let mut end = end;
+        end += input[end..].len() - input[end..].trim_start_matches(is_space).len();
+        content.push_str(&input[cursor..start]);
+        cursor = end;
+    }
+    content.push_str(&input[cursor..]);
+    content.trim().to_string()
+}
+
+impl ParsedCapture {
+    /// When the note should be stamped, `None` for right now.
+    pub fn when(&self) -> Option<When> {
+        self.date.map(|date| When {
+            date,
+            time: self.time,
+        })
+    }
+}
+
+/// Parses quick capture input, resolving relative dates against the local
+/// time `now`.
+pub fn parse(input: &str, now: NaiveDateTime) -> ParsedCapture {
+    let mut parsed = ParsedCapture {
+        content: String::new(),
+        section: None,
+        tags: Vec::new(),
+        date: None,
+        time: None,
+        priority: None,
+        duration_minutes: None,
+    };
+    let mut recognized = Vec::new();
+
+    for (index, &(start, end)) in tokens(input).iter().enumerate() {
+        let token = &input[start..end];
+        let matched = if index == 0 && parsed.section.is_none() {
+            parse_section(token).map(|section| parsed.section = Some(section))
+        } else {
+            None
+        };
+        let matched = matched
+            .or_else(|| {
+                parse_tag(token).map(|tag| {
+                    if !parsed.tags.contains(&tag) {
+                        parsed.tags.push(tag);
+                    }
+                })
+            })
+            .or_else(|| {
+                parse_date(token, now).map(|when| {
+                    parsed.date = Some(when.date);
+                    parsed.time = when.time;
+                })
+            })
+            .or_else(|| parse_priority(token).map(|priority| parsed.priority = Some(priority)))
+            .or_else(|| {
+                parse_duration(token).map(|minutes| parsed.duration_minutes = Some(minutes))
+            });
+        if matched.is_some() {
+            recognized.push((start, end));
+        }
+    }
+
+    parsed.content = strip_ranges(input, &recognized);
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Monday, 10:30
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+            .unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, month, day)
+    }
+
+    #[test]
+    fn plain_text_is_returned_unchanged() {
+        let parsed = parse("Reviewed PR for the login flow", now());
+        assert_eq!(parsed.content, "Reviewed PR for the login flow");
+        assert_eq!(parsed.section, None);
+        assert!(parsed.tags.is_empty());
+        assert_eq!(parsed.date, None);
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.duration_minutes, None);
+    }
+
+    #[test]
+    fn section_prefix_is_recognized() {
+        let parsed = parse("/todo write migration", now());
+        assert_eq!(parsed.section, Some(Section::Todo));
+        assert_eq!(parsed.content, "write migration");
+
+        let parsed = parse("/BLOCKER waiting for VPN access", now());
+        assert_eq!(parsed.section, Some(Section::Blocker));
+        assert_eq!(parsed.content, "waiting for VPN access");
+
+        assert_eq!(parse("/done deploy", now()).section, Some(Section::Done));
+    }
+
+    #[test]
+    fn section_prefix_only_counts_as_first_word() {
+        let parsed = parse("ask about /todo endpoint", now());
+        assert_eq!(parsed.section, None);
+        assert_eq!(parsed.content, "ask about /todo endpoint");
+    }
+
+    #[test]
+    fn unknown_slash_commands_and_paths_stay_untouched() {
+        let parsed = parse("/etc/hosts was broken", now());
+        assert_eq!(parsed.section, None);
+        assert_eq!(parsed.content, "/etc/hosts was broken");
+
+        assert_eq!(parse("/later maybe", now()).content, "/later maybe");
+    }
+
+    #[test]
+    fn tags_are_collected_lowercased_and_deduplicated() {
+        let parsed = parse("fixed #Auth bug in #backend #auth", now());
+        assert_eq!(parsed.tags, vec!["auth", "backend"]);
+        assert_eq!(parsed.content, "fixed bug in");
+    }
+
+    #[test]
+    fn tags_allow_separators() {
+        let parsed = parse("#team/platform #on-call #ci_cd", now());
+        assert_eq!(parsed.tags, vec!["team/platform", "on-call", "ci_cd"]);
+        assert_eq!(parsed.content, "");
+    }
+
+    #[test]
+    fn issue_numbers_and_bare_hashes_are_not_tags() {
+        let parsed = parse("closed #123 and # of items, C# code", now());
+        assert!(parsed.tags.is_empty());
+        assert_eq!(parsed.content, "closed #123 and # of items, C# code");
+    }
+
+    #[test]
+    fn tags_with_trailing_punctuation_stay_text() {
+        let parsed = parse("talked about #infra, then lunch", now());
+        assert!(parsed.tags.is_empty());
+        assert_eq!(parsed.content, "talked about #infra, then lunch");
+    }
+
+    #[test]
+    fn relative_days_resolve_against_now() {
+        assert_eq!(parse("@today x", now()).date, date(10, 19));
+        assert_eq!(parse("@yesterday x", now()).date, date(10, 18));
+        assert_eq!(parse("@gestern x", now()).date, date(10, 18));
+        assert_eq!(parse("@vorgestern x", now()).date, date(10, 17));
+    }
+
+    #[test]
+    fn weekdays_resolve_to_the_most_recent_one() {
+        assert_eq!(parse("@fri", now()).date, date(10, 16));
+        assert_eq!(parse("@Friday", now()).date, date(10, 16));
+        assert_eq!(parse("@di", now()).date, date(10, 13));
+        // Today is a Monday
+        assert_eq!(parse("@mon", now()).date, date(10, 19));
+    }
+
+    #[test]
+    fn iso_dates_are_accepted() {
+        let parsed = parse("retro notes @2026-10-02", now());
+        assert_eq!(parsed.date, date(10, 2));
+        assert_eq!(parsed.content, "retro notes");
+    }
+
+    #[test]
+    fn natural_language_dates_use_dashes_for_spaces() {
+        let parsed = parse("call with ops @gestern-16uhr", now());
+        assert_eq!(parsed.date, date(10, 18));
+        assert_eq!(parsed.time, NaiveTime::from_hms_opt(16, 0, 0));
+        assert_eq!(parsed.content, "call with ops");
+
+        let parsed = parse("@vor_2_stunden standup", now());
+        assert_eq!(parsed.date, date(10, 19));
+        assert_eq!(parsed.time, NaiveTime::from_hms_opt(8, 30, 0));
+    }
+
+    #[test]
+    fn clock_time_alone_stamps_today() {
+        let parsed = parse("lunch @12:30", now());
+        assert_eq!(
+            parsed.when(),
+            Some(When {
+                date: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+                time: NaiveTime::from_hms_opt(12, 30, 0),
+            })
+        );
+    }
+
+    #[test]
+    fn mentions_and_emails_are_not_dates() {
+        let parsed = parse("pinged @alice and mail@example.com", now());
+        assert_eq!(parsed.date, None);
+        assert_eq!(parsed.content, "pinged @alice and mail@example.com");
+    }
+
+    #[test]
+    fn priorities_are_recognized() {
+        assert_eq!(parse("x !high", now()).priority, Some(Priority::High));
+        assert_eq!(parse("x !mittel", now()).priority, Some(Priority::Medium));
+        assert_eq!(parse("x !LOW", now()).priority, Some(Priority::Low));
+    }
+
+    #[test]
+    fn unknown_priorities_and_exclamations_stay_text() {
+        let parsed = parse("it works! !important", now());
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.content, "it works! !important");
+    }
+
+    #[test]
+    fn durations_are_converted_to_minutes() {
+        assert_eq!(parse("x ~30m", now()).duration_minutes, Some(30));
+        assert_eq!(parse("x ~45min", now()).duration_minutes, Some(45));
+        assert_eq!(parse("x ~2h", now()).duration_minutes, Some(120));
+        assert_eq!(parse("x ~1h30m", now()).duration_minutes, Some(90));
+        assert_eq!(parse("x ~1.5h", now()).duration_minutes, Some(90));
+        assert_eq!(parse("x ~1,5std", now()).duration_minutes, Some(90));
+    }
+
+    #[test]
+    fn invalid_durations_stay_text() {
+        let parsed = parse("~ ~abc ~30 ~0m ~5x", now());
+        assert_eq!(parsed.duration_minutes, None);
+        assert_eq!(parsed.content, "~ ~abc ~30 ~0m ~5x");
+    }
+
+    #[test]
+    fn later_tokens_override_earlier_ones() {
+        let parsed = parse("x !low !high @mon @fri", now());
+        assert_eq!(parsed.priority, Some(Priority::High));
+        assert_eq!(parsed.date, date(10, 16));
+        assert_eq!(parsed.content, "x");
+    }
+
+    #[test]
+    fn all_tokens_combined() {
+        let parsed = parse(
+            "/blocker staging DB down #infra @yesterday !high ~1h",
+            now(),
+        );
+        assert_eq!(
+            parsed,
+            ParsedCapture {
+                content: "staging DB down".to_string(),
+                section: Some(Section::Blocker),
+                tags: vec!["infra".to_string()],
+                date: date(10, 18),
+                time: None,
+                priority: Some(Priority::High),
+                duration_minutes: Some(60),
+            }
+        );
+    }
+
+    #[test]
+    fn tokens_in_the_middle_keep_surrounding_text_intact() {
+        let parsed = parse("deploy  #ops   went fine\nnext line", now());
+        assert_eq!(parsed.content, "deploy  went fine\nnext line");
+    }
+
+    #[test]
+    fn input_of_only_tokens_gives_empty_content() {
+        let parsed = parse("/todo #x", now());
+        assert_eq!(parsed.content, "");
+        assert_eq!(parsed.section, Some(Section::Todo));
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::{
+    DateTime, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// Hour at which a new day begins, so notes written after midnight can still
+// count towards the previous day. Mirrors the setting, see `rollover`.
+static DAY_START_HOUR: AtomicU32 = AtomicU32::new(0);
+
+pub fn set_day_start_hour(hour: u32) {
+    DAY_START_HOUR.store(hour.min(23), Ordering::Relaxed);
+}
+
+pub fn day_start_hour() -> u32 {
+    DAY_START_HOUR.load(Ordering::Relaxed)
+}
+
+/// Day a local time belongs to when days begin at `start_hour`.
+pub fn logical_day(local: NaiveDateTime, start_hour: u32) -> NaiveDate {
+    (local - Duration::hours(start_hour as i64)).date()
+}
+
+pub fn today() -> NaiveDate {
+    logical_day(Local::now().naive_local(), day_start_hour())
+}
+
+fn to_utc(local: NaiveDateTime) -> DateTime<Utc> {
+    match Local.from_local_datetime(&local).earliest() {
+        Some(local) => local.with_timezone(&Utc),
+        // Time skipped by a DST jump, treat the naive time as UTC
+        None => local.and_utc(),
+    }
+}
+
+/// UTC instant at which the given local day begins.
+pub fn start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    let start = NaiveTime::from_hms_opt(day_start_hour(), 0, 0).unwrap_or(NaiveTime::MIN);
+    to_utc(day.and_time(start))
+}
+
+/// Half-open UTC range `[start, end)` covering the given local day.
+pub fn day_range(day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    days_range(day, day)
+}
+
+/// Half-open UTC range covering the local days `from` through `to`.
+pub fn days_range(from: NaiveDate, to: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let next = to.succ_opt().unwrap_or(to);
+    (start_of_day(from), start_of_day(next))
+}
+
+/// Every day from `from` through `to`.
+pub fn each_day(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    from.iter_days().take_while(move |day| *day <= to)
+}
+
+/// First and last day of a month, or of the whole year without a month.
+pub fn period(year: i32, month: Option<u32>) -> Option<(NaiveDate, NaiveDate)> {
+    let (first, next) = match month {
+        Some(month) => {
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            (first, first.checked_add_months(Months::new(1))?)
+        }
+        None => (
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+        ),
+    };
+    Some((first, next.pred_opt()?))
+}
+
+/// SQLite modifiers that bucket a stored timestamp into its local day, for
+/// `date(created_at, 'localtime', ?)`. Matches `local_day_of`.
+pub fn sqlite_day_shift() -> String {
+    format!("-{} hours", day_start_hour())
+}
+
+/// Local day of a stored RFC 3339 timestamp.
+pub fn local_day_of(timestamp: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| logical_day(dt.with_timezone(&Local).naive_local(), day_start_hour()))
+}
+
+/// A stored timestamp moved to `day`, keeping its local time of day.
+pub fn same_time_on(timestamp: &str, day: NaiveDate) -> Option<DateTime<Utc>> {
+    let local = DateTime::parse_from_rfc3339(timestamp)
+        .ok()?
+        .with_timezone(&Local)
+        .naive_local();
+    let from = logical_day(local, day_start_hour());
+    Some(to_utc(local + (day - from)))
+}
+
+/// The current local time of day on `day`, used when a note is backdated.
+pub fn now_on(day: NaiveDate) -> DateTime<Utc> {
+    let now = Local::now();
+    if today() == day {
+        return now.with_timezone(&Utc);
+    }
+    let time = now.time();
+    // Early morning hours belong to the previous day's date
+    let date = if time.hour() < day_start_hour() {
+        day.succ_opt().unwrap_or(day)
+    } else {
+        day
+    };
+    to_utc(date.and_time(time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 30, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn days_start_at_midnight_by_default() {
+        assert_eq!(logical_day(at(19, 0), 0), at(19, 0).date());
+        assert_eq!(logical_day(at(19, 23), 0), at(19, 0).date());
+    }
+
+    #[test]
+    fn early_hours_belong_to_previous_day_with_later_start() {
+        assert_eq!(logical_day(at(20, 2), 4), at(19, 0).date());
+        assert_eq!(logical_day(at(20, 4), 4), at(20, 0).date());
+    }
+
+    #[test]
+    fn periods_cover_whole_months_and_years() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        assert_eq!(period(2026, Some(2)), Some((date(2, 1), date(2, 28))));
+        assert_eq!(period(2026, Some(12)), Some((date(12, 1), date(12, 31))));
+        assert_eq!(period(2026, None), Some((date(1, 1), date(12, 31))));
+        assert_eq!(period(2026, Some(13)), None);
+        assert_eq!(each_day(date(2, 27), date(3, 1)).count(), 3);
+    }
+
+    #[test]
+    fn moving_to_another_day_keeps_the_time() {
+        let stamp = to_utc(at(19, 9)).to_rfc3339();
+        let moved = same_time_on(&stamp, at(12, 0).date()).unwrap();
+        assert_eq!(moved, to_utc(at(12, 9)));
+        assert_eq!(same_time_on("yesterday", at(12, 0).date()), None);
+    }
+}
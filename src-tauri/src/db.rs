@@ -0,0 +1,160 @@
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_sql::{DbInstances, DbPool, Migration, MigrationKind};
+
+pub const DB_URL: &str = "sqlite:daily-notes.db";
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            description: "create_notes_table",
+            sql: "CREATE TABLE IF NOT EXISTS notes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                is_quick_capture BOOLEAN NOT NULL DEFAULT 0
+            );",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 2,
+            description: "create_notes_index",
+            sql: "CREATE INDEX IF NOT EXISTS idx_notes_created_at ON notes(created_at);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 3,
+            description: "add_capture_fields_and_tags",
+            sql: "ALTER TABLE notes ADD COLUMN section TEXT;
+            ALTER TABLE notes ADD COLUMN priority TEXT;
+            ALTER TABLE notes ADD COLUMN duration_minutes INTEGER;
+            CREATE TABLE IF NOT EXISTS note_tags (
+                note_id INTEGER NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (note_id, tag)
+            );
+            CREATE INDEX IF NOT EXISTS idx_note_tags_tag ON note_tags(tag);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 4,
+            description: "add_task_state",
+            sql: "ALTER TABLE notes ADD COLUMN task_state TEXT;
+            ALTER TABLE notes ADD COLUMN completed_at TEXT;
+            CREATE INDEX IF NOT EXISTS idx_notes_task_state ON notes(task_state);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 5,
+            description: "create_reminders_table",
+            sql: "CREATE TABLE IF NOT EXISTS reminders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                time_of_day TEXT,
+                workdays_only BOOLEAN NOT NULL DEFAULT 1,
+                due_at TEXT,
+                note_id INTEGER REFERENCES notes(id) ON DELETE CASCADE,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                last_fired_at TEXT
+            );
+            INSERT INTO reminders (kind, time_of_day, enabled) VALUES ('standup', '09:15', 0);
+            INSERT INTO reminders (kind, time_of_day, enabled) VALUES ('end_of_day', '17:30', 0);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 6,
+            description: "add_inbox_processed_at",
+            // Captures older than the 48 hour list count as already processed
+            sql: "ALTER TABLE notes ADD COLUMN processed_at TEXT;
+            UPDATE notes SET processed_at = updated_at
+                WHERE is_quick_capture = 1
+                AND created_at < strftime('%Y-%m-%dT%H:%M:%fZ', 'now', '-2 days');
+            CREATE INDEX IF NOT EXISTS idx_notes_inbox
+                ON notes(created_at) WHERE is_quick_capture = 1 AND processed_at IS NULL;",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 7,
+            description: "add_pinned_and_starred",
+            sql: "ALTER TABLE notes ADD COLUMN pin_order INTEGER;
+            ALTER TABLE notes ADD COLUMN starred BOOLEAN NOT NULL DEFAULT 0;
+            CREATE INDEX IF NOT EXISTS idx_notes_pinned
+                ON notes(pin_order) WHERE pin_order IS NOT NULL;",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 8,
+            description: "create_note_links_table",
+            // Targets have no foreign key so links to deleted notes can be reported
+            sql: "CREATE TABLE IF NOT EXISTS note_links (
+                source_id INTEGER NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+                target_id INTEGER,
+                target_day TEXT,
+                CHECK ((target_id IS NULL) != (target_day IS NULL))
+            );
+            CREATE INDEX IF NOT EXISTS idx_note_links_source ON note_links(source_id);
+            CREATE INDEX IF NOT EXISTS idx_note_links_target ON note_links(target_id);
+            CREATE INDEX IF NOT EXISTS idx_note_links_day ON note_links(target_day);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 9,
+            description: "create_attachments_table",
+            // Files live in the attachments directory, named by `hash`
+            sql: "CREATE TABLE IF NOT EXISTS attachments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                note_id INTEGER NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+                name TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_attachments_note ON attachments(note_id);
+            CREATE INDEX IF NOT EXISTS idx_attachments_hash ON attachments(hash);",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 10,
+            description: "add_archived_at",
+            sql: "ALTER TABLE notes ADD COLUMN archived_at TEXT;
+            CREATE INDEX IF NOT EXISTS idx_notes_archived ON notes(archived_at);",
+            kind: MigrationKind::Up,
+        },
+    ]
+}
+
+/// Fresh in-memory database with every migration applied.
+#[cfg(test)]
+pub async fn test_pool() -> SqlitePool {
+    // Each connection to `:memory:` opens its own empty database
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    for migration in migrations() {
+        sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+    }
+    pool
+}
+
+/// Returns the pool the SQL plugin opened for `DB_URL` during preload.
+pub async fn pool(app: &AppHandle) -> Result<SqlitePool, String> {
+    let instances = app.state::<DbInstances>();
+    let instances = instances.0.read().await;
+    match instances.get(DB_URL) {
+        Some(DbPool::Sqlite(pool)) => Ok(pool.clone()),
+        _ => Err("Database not loaded".to_string()),
+    }
+}
+
+/// Directory attachment files are stored in, next to the database.
+pub fn attachments_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("attachments"))
+        .map_err(|e| format!("Failed to resolve data directory: {}", e))
+}
@@ -0,0 +1,202 @@
+// Events sent to the webviews and Rust listeners. Every change to notes goes
+// out as one `notes-updated` event with the same payload shape, published by
+// the command once its transaction has committed.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::clipboard::ClipPreview;
+use crate::days;
+use crate::notes::NoteEntry;
+
+pub const NOTES_UPDATED: &str = "notes-updated";
+pub const DAY_CHANGED: &str = "day-changed";
+pub const CLIPBOARD_CAPTURE: &str = "clipboard-capture";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+    /// Several of the above at once, e.g. a bulk change or its undo
+    Bulk,
+}
+
+impl ChangeKind {
+    /// The kind of an operation made of changes of `kinds`.
+    pub fn combined(kinds: &[ChangeKind]) -> Option<ChangeKind> {
+        match kinds {
+            [] => None,
+            [first, rest @ ..] if rest.iter().all(|kind| kind == first) => Some(*first),
+            _ => Some(ChangeKind::Bulk),
+        }
+    }
+}
+
+/// Payload of `notes-updated`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NotesChanged {
+    pub kind: ChangeKind,
+    pub ids: Vec<i64>,
+    /// Local days whose notes changed, both days when a note was moved
+    pub days: Vec<NaiveDate>,
+}
+
+impl NotesChanged {
+    /// Change to `notes`, which may list the same note before and after.
+    pub fn new(kind: ChangeKind, notes: &[&NoteEntry]) -> NotesChanged {
+        let mut ids: Vec<i64> = Vec::new();
+        let mut changed_days: Vec<NaiveDate> = Vec::new();
+        for note in notes {
+            if !ids.contains(&note.id) {
+                ids.push(note.id);
+            }
+            if let Some(day) = days::local_day_of(&note.created_at) {
+                if !changed_days.contains(&day) {
+                    changed_days.push(day);
+                }
+            }
+        }
+        changed_days.sort();
+        NotesChanged {
+            kind,
+            ids,
+            days: changed_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent {
+    NotesChanged(NotesChanged),
+    /// The new local day, `YYYY-MM-DD`
+    DayChanged(NaiveDate),
+    /// A clip waiting for confirmation in quick capture
+    ClipboardCapture(ClipPreview),
+}
+
+impl AppEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::NotesChanged(_) => NOTES_UPDATED,
+            AppEvent::DayChanged(_) => DAY_CHANGED,
+            AppEvent::ClipboardCapture(_) => CLIPBOARD_CAPTURE,
+        }
+    }
+}
+
+/// Where events go, the app handle outside of tests.
+pub trait EventSink {
+    fn send(&self, event: &AppEvent);
+}
+
+impl<R: Runtime> EventSink for AppHandle<R> {
+    fn send(&self, event: &AppEvent) {
+        if let Err(e) = self.emit(event.name(), event) {
+            eprintln!("Failed to emit {}: {}", event.name(), e);
+        }
+    }
+}
+
+pub fn notes_changed(sink: &impl EventSink, kind: ChangeKind, notes: &[&NoteEntry]) {
+    sink.send(&AppEvent::NotesChanged(NotesChanged::new(kind, notes)));
+}
+
+pub fn day_changed(sink: &impl EventSink, day: NaiveDate) {
+    sink.send(&AppEvent::DayChanged(day));
+}
+
+pub fn clipboard_capture(sink: &impl EventSink, preview: ClipPreview) {
+    sink.send(&AppEvent::ClipboardCapture(preview));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes;
+    use chrono::Duration;
+    use serde_json::{json, Value};
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<(&'static str, Value)>>);
+
+    impl EventSink for Recorder {
+        fn send(&self, event: &AppEvent) {
+            let payload = serde_json::to_value(event).unwrap();
+            self.0.borrow_mut().push((event.name(), payload));
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn note(id: i64, on: u32) -> NoteEntry {
+        let created_at = notes::timestamp(&(days::start_of_day(day(on)) + Duration::hours(9)));
+        NoteEntry {
+            id,
+            content: "Standup".to_string(),
+            created_at: created_at.clone(),
+            updated_at: created_at,
+            is_quick_capture: false,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state: None,
+            completed_at: None,
+            processed_at: None,
+            pin_order: None,
+            starred: false,
+            archived_at: None,
+        }
+    }
+
+    #[test]
+    fn records_events_in_order_with_one_payload_shape() {
+        let recorder = Recorder::default();
+        notes_changed(&recorder, ChangeKind::Created, &[&note(1, 19)]);
+        notes_changed(&recorder, ChangeKind::Deleted, &[&note(1, 19)]);
+        day_changed(&recorder, day(20));
+
+        assert_eq!(
+            recorder.0.into_inner(),
+            vec![
+                (
+                    NOTES_UPDATED,
+                    json!({ "kind": "created", "ids": [1], "days": ["2026-10-19"] })
+                ),
+                (
+                    NOTES_UPDATED,
+                    json!({ "kind": "deleted", "ids": [1], "days": ["2026-10-19"] })
+                ),
+                (DAY_CHANGED, json!("2026-10-20")),
+            ]
+        );
+    }
+
+    #[test]
+    fn operations_of_several_kinds_are_one_bulk_change() {
+        use ChangeKind::*;
+        assert_eq!(ChangeKind::combined(&[]), None);
+        assert_eq!(ChangeKind::combined(&[Updated, Updated]), Some(Updated));
+        assert_eq!(ChangeKind::combined(&[Updated, Deleted]), Some(Bulk));
+        assert_eq!(
+            serde_json::to_value(ChangeKind::Bulk).unwrap(),
+            json!("bulk")
+        );
+    }
+
+    #[test]
+    fn moved_notes_report_both_days_once() {
+        let before = note(7, 19);
+        let after = note(7, 16);
+        let change = NotesChanged::new(ChangeKind::Updated, &[&before, &after]);
+        assert_eq!(change.ids, vec![7]);
+        assert_eq!(change.days, vec![day(16), day(19)]);
+    }
+}
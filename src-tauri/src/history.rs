@@ -0,0 +1,636 @@
+// Undo/redo for note operations. An operation keeps every note it touched as
+// it was before and after, so undo restores the "before" states and redo the
+// "after" states. The log lives in memory: it survives hiding the windows,
+// not a restart. Notes changed outside the log in the meantime, by another
+// operation or a background job, are never overwritten: stepping checks that
+// every note is still in the state the log expects.
+
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::attachments::{self, Attachment};
+use crate::notes::{self, NoteEntry, NOTE_COLUMNS};
+use crate::reminders::{self, Reminder};
+
+const HISTORY_LIMIT: usize = 100;
+
+/// Rows the foreign keys delete together with a note. Kept with the change
+/// so the note comes back complete.
+#[derive(Debug, Clone, Default)]
+pub struct Dependents {
+    pub attachments: Vec<Attachment>,
+    pub reminders: Vec<Reminder>,
+    /// Note the attachments were moved to instead, e.g. by a merge
+    pub moved_to: Option<i64>,
+}
+
+impl Dependents {
+    /// The rows of note `id`, read before it is deleted.
+    pub async fn load(conn: &mut SqliteConnection, id: i64) -> Result<Dependents, String> {
+        Ok(Dependents {
+            attachments: attachments::of_note(conn, id).await?,
+            reminders: reminders::of_note(conn, id).await?,
+            moved_to: None,
+        })
+    }
+
+    /// Reads the rows of note `id` and moves its attachments to note `to`,
+    /// before the note is deleted.
+    pub async fn move_to(
+        conn: &mut SqliteConnection,
+        id: i64,
+        to: i64,
+    ) -> Result<Dependents, String> {
+        let dependents = Dependents {
+            moved_to: Some(to),
+            ..Dependents::load(conn, id).await?
+        };
+        attachments::reassign(conn, id, to).await?;
+        Ok(dependents)
+    }
+
+    /// Writes the rows back once their note exists again, moved attachments
+    /// go back to it.
+    async fn restore(&self, conn: &mut SqliteConnection) -> Result<(), String> {
+        for attachment in &self.attachments {
+            attachments::reinsert(conn, attachment).await?;
+        }
+        for reminder in &self.reminders {
+            reminders::reinsert(conn, reminder).await?;
+        }
+        Ok(())
+    }
+}
+
+/// One note before and after an operation, `None` where it did not exist.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub id: i64,
+    pub before: Option<NoteEntry>,
+    pub after: Option<NoteEntry>,
+    /// What went with the note the last time it was deleted
+    pub dependents: Dependents,
+}
+
+impl Change {
+    pub fn created(note: &NoteEntry) -> Change {
+        Change {
+            id: note.id,
+            before: None,
+            after: Some(note.clone()),
+            dependents: Dependents::default(),
+        }
+    }
+
+    pub fn updated(before: &NoteEntry, after: &NoteEntry) -> Change {
+        Change {
+            id: after.id,
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+            dependents: Dependents::default(),
+        }
+    }
+
+    pub fn deleted(note: &NoteEntry, dependents: Dependents) -> Change {
+        Change {
+            id: note.id,
+            before: Some(note.clone()),
+            after: None,
+            dependents,
+        }
+    }
+
+    /// Current and target state when stepping in `direction`.
+    pub fn states(&self, direction: Direction) -> (Option<&NoteEntry>, Option<&NoteEntry>) {
+        match direction {
+            Direction::Undo => (self.after.as_ref(), self.before.as_ref()),
+            Direction::Redo => (self.before.as_ref(), self.after.as_ref()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub changes: Vec<Change>,
+    /// Saved from the quick capture window, see `History::pop_last_capture`
+    pub quick_capture: bool,
+}
+
+impl Operation {
+    pub fn new(changes: Vec<Change>) -> Operation {
+        Operation {
+            changes,
+            quick_capture: false,
+        }
+    }
+
+    pub fn quick_capture(note: &NoteEntry) -> Operation {
+        Operation {
+            changes: vec![Change::created(note)],
+            quick_capture: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Undo,
+    Redo,
+}
+
+#[derive(Debug)]
+pub struct History {
+    undo: Vec<Operation>,
+    redo: Vec<Operation>,
+    limit: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::with_limit(HISTORY_LIMIT)
+    }
+}
+
+pub type HistoryState = Mutex<History>;
+
+impl History {
+    pub fn with_limit(limit: usize) -> History {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Records a new operation, which makes everything undone so far final.
+    pub fn record(&mut self, operation: Operation) {
+        if operation.changes.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.push(Direction::Undo, operation);
+    }
+
+    /// Next operation to step in `direction`.
+    pub fn pop(&mut self, direction: Direction) -> Option<Operation> {
+        match direction {
+            Direction::Undo => self.undo.pop(),
+            Direction::Redo => self.redo.pop(),
+        }
+    }
+
+    /// Puts an operation on the stack `direction` takes from, e.g. a
+    /// redone operation back on the undo stack.
+    pub fn push(&mut self, direction: Direction, operation: Operation) {
+        let stack = match direction {
+            Direction::Undo => &mut self.undo,
+            Direction::Redo => &mut self.redo,
+        };
+        stack.push(operation);
+        if stack.len() > self.limit {
+            stack.remove(0);
+        }
+    }
+
+    /// Takes the latest quick capture out of the undo stack, even if other
+    /// operations came after it. Later changes to the captured note are
+    /// folded into it, undoing them on their own would bring the note back.
+    pub fn pop_last_capture(&mut self) -> Option<Operation> {
+        let index = self.undo.iter().rposition(|op| op.quick_capture)?;
+        let mut capture = self.undo.remove(index);
+        for operation in &mut self.undo[index..] {
+            operation.changes.retain(|change| {
+                match capture.changes.iter_mut().find(|own| own.id == change.id) {
+                    Some(own) => {
+                        own.after = change.after.clone();
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+        self.undo.retain(|op| !op.changes.is_empty());
+        let ids: Vec<i64> = capture.changes.iter().map(|c| c.id).collect();
+        self.redo
+            .retain(|op| !op.changes.iter().any(|c| ids.contains(&c.id)));
+        Some(capture)
+    }
+
+    /// Records a change the app made on its own to a note, e.g. a fetched
+    /// link title. Belongs to the note's quick capture if nothing touched
+    /// the note since, undoing the capture then undoes both at once.
+    pub fn record_follow_up(&mut self, change: Change) {
+        let last = self.undo.iter_mut().rev().find_map(|op| {
+            let quick_capture = op.quick_capture;
+            op.changes
+                .iter_mut()
+                .find(|own| own.id == change.id)
+                .map(|own| (quick_capture, own))
+        });
+        match last {
+            Some((true, own)) if own.after == change.before => own.after = change.after,
+            _ => self.record(Operation::new(vec![change])),
+        }
+    }
+
+    /// Files of deleted notes that undo or redo may still bring back.
+    pub fn attachment_hashes(&self) -> HashSet<String> {
+        self.undo
+            .iter()
+            .chain(&self.redo)
+            .flat_map(|op| &op.changes)
+            .flat_map(|change| &change.dependents.attachments)
+            .map(|attachment| attachment.hash.clone())
+            .collect()
+    }
+}
+
+/// Whether note `id` is stored exactly as `expected`.
+async fn is_unchanged(
+    conn: &mut SqliteConnection,
+    id: i64,
+    expected: Option<&NoteEntry>,
+) -> Result<bool, String> {
+    let stored = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE id = ?1",
+        NOTE_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to fetch note: {}", e))?;
+    Ok(match (expected, stored) {
+        (None, None) => true,
+        (Some(expected), Some(stored)) => {
+            // Tags are read back sorted and without duplicates
+            let mut expected = expected.clone();
+            expected.tags.sort();
+            expected.tags.dedup();
+            expected == stored
+        }
+        _ => false,
+    })
+}
+
+/// Steps `operation` in `direction` in one transaction. Returns false and
+/// changes nothing when a note is no longer in the state the operation left
+/// it in. Notes deleted on the way keep their attachments and reminders in
+/// `operation` for the way back.
+pub async fn apply(
+    pool: &SqlitePool,
+    operation: &mut Operation,
+    direction: Direction,
+) -> Result<bool, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    // Later changes first when undoing, in case one builds on another
+    let changes: Box<dyn Iterator<Item = &mut Change>> = match direction {
+        Direction::Undo => Box::new(operation.changes.iter_mut().rev()),
+        Direction::Redo => Box::new(operation.changes.iter_mut()),
+    };
+    // A note can appear twice, e.g. relinked and then deleted by a bulk
+    // delete. Only its first state is checked, the rest builds on that.
+    let mut checked = Vec::new();
+    for change in changes {
+        let (current, target) = change.states(direction);
+        if !checked.contains(&change.id) {
+            if !is_unchanged(&mut tx, change.id, current).await? {
+                return Ok(false);
+            }
+            checked.push(change.id);
+        }
+        let (existed, exists) = (current.is_some(), target.is_some());
+        if existed && !exists {
+            change.dependents = match change.dependents.moved_to {
+                Some(to) => Dependents::move_to(&mut tx, change.id, to).await?,
+                None => Dependents::load(&mut tx, change.id).await?,
+            };
+        }
+        let (_, target) = change.states(direction);
+        notes::restore(&mut tx, change.id, target).await?;
+        if exists && !existed {
+            change.dependents.restore(&mut tx).await?;
+        }
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit undo: {}", e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use chrono::Utc;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    fn note(id: i64, content: &str) -> NoteEntry {
+        NoteEntry {
+            id,
+            content: content.to_string(),
+            created_at: "2026-10-19T08:00:00.000Z".to_string(),
+            updated_at: "2026-10-19T08:00:00.000Z".to_string(),
+            is_quick_capture: false,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state: None,
+            completed_at: None,
+            processed_at: None,
+            pin_order: None,
+            starred: false,
+            archived_at: None,
+        }
+    }
+
+    fn created(id: i64) -> Operation {
+        Operation::new(vec![Change::created(&note(id, "x"))])
+    }
+
+    fn ids(operation: Option<Operation>) -> Vec<i64> {
+        operation
+            .map(|op| op.changes.iter().map(|c| c.id).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn undo_and_redo_move_between_stacks() {
+        let mut history = History::default();
+        history.record(created(1));
+        history.record(created(2));
+
+        let undone = history.pop(Direction::Undo).unwrap();
+        assert_eq!(undone.changes[0].id, 2);
+        history.push(Direction::Redo, undone);
+
+        let redone = history.pop(Direction::Redo).unwrap();
+        history.push(Direction::Undo, redone);
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![2]);
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![1]);
+        assert!(history.pop(Direction::Undo).is_none());
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let mut history = History::default();
+        history.record(created(1));
+        let undone = history.pop(Direction::Undo).unwrap();
+        history.push(Direction::Redo, undone);
+        history.record(created(2));
+        assert!(history.pop(Direction::Redo).is_none());
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = History::with_limit(2);
+        for id in 1..=3 {
+            history.record(created(id));
+        }
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![3]);
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![2]);
+        assert!(history.pop(Direction::Undo).is_none());
+    }
+
+    #[test]
+    fn last_capture_is_found_behind_later_operations() {
+        let mut history = History::default();
+        history.record(Operation::quick_capture(&note(1, "first")));
+        history.record(Operation::quick_capture(&note(2, "second")));
+        history.record(created(3));
+
+        assert_eq!(ids(history.pop_last_capture()), vec![2]);
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![3]);
+        assert_eq!(ids(history.pop_last_capture()), vec![1]);
+        assert!(history.pop_last_capture().is_none());
+    }
+
+    #[test]
+    fn states_swap_with_direction() {
+        let change = Change::updated(&note(1, "old"), &note(1, "new"));
+        let (current, target) = change.states(Direction::Undo);
+        assert_eq!(current.map(|n| n.content.as_str()), Some("new"));
+        assert_eq!(target.map(|n| n.content.as_str()), Some("old"));
+        let (_, target) = change.states(Direction::Redo);
+        assert_eq!(target.map(|n| n.content.as_str()), Some("new"));
+    }
+
+    #[test]
+    fn later_edits_are_folded_into_the_last_capture() {
+        let mut history = History::default();
+        let capture = note(1, "capture");
+        history.record(Operation::quick_capture(&capture));
+        history.record(Operation::new(vec![Change::updated(
+            &capture,
+            &note(1, "edited"),
+        )]));
+        history.record(Operation::new(vec![
+            Change::updated(&note(1, "edited"), &note(1, "tagged")),
+            Change::updated(&note(2, "other"), &note(2, "tagged")),
+        ]));
+
+        let popped = history.pop_last_capture().unwrap();
+        let (current, target) = popped.changes[0].states(Direction::Undo);
+        assert_eq!(current.map(|n| n.content.as_str()), Some("tagged"));
+        assert!(target.is_none());
+        // Only the change to the other note is left to undo
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![2]);
+        assert!(history.pop(Direction::Undo).is_none());
+    }
+
+    #[test]
+    fn follow_ups_join_an_untouched_capture() {
+        let mut history = History::default();
+        let capture = note(1, "[github.com](https://github.com)");
+        let titled = note(1, "[GitHub](https://github.com)");
+        history.record(Operation::quick_capture(&capture));
+        history.record(created(2));
+        history.record_follow_up(Change::updated(&capture, &titled));
+
+        assert_eq!(ids(history.pop(Direction::Undo)), vec![2]);
+        let popped = history.pop(Direction::Undo).unwrap();
+        assert_eq!(popped.changes.len(), 1);
+        assert_eq!(popped.changes[0].after.as_ref(), Some(&titled));
+
+        // An edit in between gets its own undo step
+        let edited = note(1, "[github.com](https://github.com) !");
+        history.record(Operation::quick_capture(&capture));
+        history.record(Operation::new(vec![Change::updated(&capture, &edited)]));
+        history.record_follow_up(Change::updated(&edited, &titled));
+        assert_eq!(
+            history.pop(Direction::Undo).unwrap().changes[0].before,
+            Some(edited)
+        );
+    }
+
+    async fn insert(pool: &SqlitePool, content: &str) -> NoteEntry {
+        notes::insert_note(pool, &notes::NewNote::text(content, Utc::now()))
+            .await
+            .unwrap()
+    }
+
+    async fn edit(pool: &SqlitePool, note: &NoteEntry, content: &str) -> NoteEntry {
+        let mut after = note.clone();
+        after.content = content.to_string();
+        let mut conn = pool.acquire().await.unwrap();
+        notes::restore(&mut conn, note.id, Some(&after))
+            .await
+            .unwrap();
+        after
+    }
+
+    #[tokio::test]
+    async fn undo_and_redo_restore_unchanged_notes() {
+        let pool = db::test_pool().await;
+        let note = insert(&pool, "first").await;
+        let edited = edit(&pool, &note, "second").await;
+        let mut operation = Operation::new(vec![Change::updated(&note, &edited)]);
+
+        assert!(apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        let stored = notes::get_note(&pool, note.id).await.unwrap();
+        assert_eq!(stored.map(|n| n.content), Some("first".to_string()));
+        assert!(apply(&pool, &mut operation, Direction::Redo).await.unwrap());
+        let stored = notes::get_note(&pool, note.id).await.unwrap();
+        assert_eq!(stored.map(|n| n.content), Some("second".to_string()));
+    }
+
+    #[tokio::test]
+    async fn undo_keeps_changes_made_outside_the_history() {
+        let pool = db::test_pool().await;
+        let note = insert(&pool, "first").await;
+        let edited = edit(&pool, &note, "second").await;
+        let mut operation = Operation::new(vec![Change::updated(&note, &edited)]);
+        // E.g. archived by the scheduler
+        let mut archived = edited.clone();
+        archived.archived_at = Some(notes::timestamp(&Utc::now()));
+        let mut conn = pool.acquire().await.unwrap();
+        notes::restore(&mut conn, note.id, Some(&archived))
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert!(!apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        let stored = notes::get_note(&pool, note.id).await.unwrap().unwrap();
+        assert_eq!(stored, archived);
+    }
+
+    #[tokio::test]
+    async fn undo_does_not_bring_back_deleted_notes() {
+        let pool = db::test_pool().await;
+        let note = insert(&pool, "first").await;
+        let other = insert(&pool, "other").await;
+        let edited = edit(&pool, &note, "second").await;
+        let other_edited = edit(&pool, &other, "changed").await;
+        let mut operation = Operation::new(vec![
+            Change::updated(&other, &other_edited),
+            Change::updated(&note, &edited),
+        ]);
+        // E.g. removed by capture retention
+        notes::delete_note(&pool, note.id).await.unwrap();
+
+        assert!(!apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        assert!(notes::get_note(&pool, note.id).await.unwrap().is_none());
+        // Nothing of the operation is applied
+        let stored = notes::get_note(&pool, other.id).await.unwrap();
+        assert_eq!(stored.map(|n| n.content), Some("changed".to_string()));
+    }
+
+    #[tokio::test]
+    async fn undoing_a_deletion_brings_back_attachments_and_reminders() {
+        let pool = db::test_pool().await;
+        let root = std::env::temp_dir().join(format!("daily-history-{}", std::process::id()));
+        let note = insert(&pool, "Deploy failed").await;
+        let attachment = attachments::add(&pool, &root, note.id, "deploy.log", b"exit 1")
+            .await
+            .unwrap();
+        let blob = attachments::blob_path(&root, &attachment.hash);
+        // Past the grace period of the garbage collection
+        fs::File::options()
+            .write(true)
+            .open(&blob)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(2 * 60 * 60))
+            .unwrap();
+        let reminder = reminders::add_note_reminder(&pool, note.id, &Utc::now())
+            .await
+            .unwrap();
+
+        let mut history = History::default();
+        history.record(Operation::new(
+            notes::delete_note(&pool, note.id).await.unwrap(),
+        ));
+        assert!(reminders::get(&pool, reminder.id).await.is_err());
+        attachments::collect_garbage(&pool, &root, &history.attachment_hashes())
+            .await
+            .unwrap();
+        assert!(blob.exists());
+
+        let mut operation = history.pop(Direction::Undo).unwrap();
+        assert!(apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        let restored = attachments::for_notes(&pool, &[note.id]).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, attachment.id);
+        let restored = reminders::get(&pool, reminder.id).await.unwrap();
+        assert_eq!(restored.note_id, Some(note.id));
+        let removed = attachments::collect_garbage(&pool, &root, &HashSet::new())
+            .await
+            .unwrap();
+        assert_eq!(removed, 0);
+        assert!(blob.exists());
+
+        // Redo deletes them again and keeps them for the next undo
+        assert!(apply(&pool, &mut operation, Direction::Redo).await.unwrap());
+        assert!(reminders::get(&pool, reminder.id).await.is_err());
+        assert!(apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        assert!(reminders::get(&pool, reminder.id).await.is_ok());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn deleting_a_shared_attachment_keeps_the_blob_for_undo() {
+        let pool = db::test_pool().await;
+        let root = std::env::temp_dir().join(format!("daily-shared-{}", std::process::id()));
+        let note = insert(&pool, "Deploy failed").await;
+        let other = insert(&pool, "Deploy failed again").await;
+        attachments::add(&pool, &root, note.id, "deploy.log", b"exit 1")
+            .await
+            .unwrap();
+        let copy = attachments::add(&pool, &root, other.id, "deploy.log", b"exit 1")
+            .await
+            .unwrap();
+        let blob = attachments::blob_path(&root, &copy.hash);
+        fs::File::options()
+            .write(true)
+            .open(&blob)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(2 * 60 * 60))
+            .unwrap();
+
+        let mut history = History::default();
+        history.record(Operation::new(
+            notes::delete_note(&pool, note.id).await.unwrap(),
+        ));
+        attachments::delete(&pool, copy.id).await.unwrap();
+        attachments::collect_garbage(&pool, &root, &history.attachment_hashes())
+            .await
+            .unwrap();
+        assert!(blob.exists());
+
+        let mut operation = history.pop(Direction::Undo).unwrap();
+        assert!(apply(&pool, &mut operation, Direction::Undo).await.unwrap());
+        assert_eq!(
+            attachments::for_notes(&pool, &[note.id])
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(blob.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,295 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    De,
+    En,
+}
+
+impl Locale {
+    /// Parses OS style tags such as `de-DE`, `de_AT.UTF-8` or `en`.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_', '.']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "de" => Some(Locale::De),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+}
+
+/// Locale reported by the OS, English if it is not one we ship.
+pub fn system_locale() -> Locale {
+    sys_locale::get_locale()
+        .and_then(|tag| Locale::from_tag(&tag))
+        .unwrap_or(Locale::En)
+}
+
+/// The settings override wins over the OS locale.
+pub fn resolve(preferred: Option<Locale>) -> Locale {
+    preferred.unwrap_or_else(system_locale)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    TrayNoNotesToday,
+    TrayCopyStandup,
+    TrayQuickCapture,
+    TrayRecentDays,
+    TrayNoRecentDays,
+    TrayShowNotes,
+    TraySettings,
+    TrayQuit,
+    /// Placeholder: `{count}`
+    TrayTooltip,
+    MainWindowTitle,
+    QuickCaptureWindowTitle,
+    SettingsWindowTitle,
+    /// Placeholder: `{date}`
+    StandupHeading,
+    ReminderStandupTitle,
+    ReminderStandupBody,
+    ReminderEndOfDayTitle,
+    ReminderEndOfDayBody,
+    ReminderNoteTitle,
+    ReminderOpen,
+    TrayCopySprintSummary,
+    /// Placeholders: `{from}`, `{to}`
+    SummaryHeading,
+    /// Placeholder: `{count}`
+    SummaryTotal,
+    SummaryByDay,
+    SummaryByTag,
+    SummaryBySection,
+    SummaryDoneTasks,
+    SummaryRecurringBlockers,
+    SummaryUntagged,
+    SectionDone,
+    SectionTodo,
+    SectionBlocker,
+    ClipboardImage,
+}
+
+fn de(msg: Msg) -> &'static str {
+    match msg {
+        Msg::TrayNoNotesToday => "Heute noch keine Notizen",
+        Msg::TrayCopyStandup => "Standup kopieren",
+        Msg::TrayQuickCapture => "Neue Quick Capture",
+        Msg::TrayRecentDays => "Letzte Tage",
+        Msg::TrayNoRecentDays => "Keine Einträge",
+        Msg::TrayShowNotes => "Notizen anzeigen",
+        Msg::TraySettings => "Einstellungen",
+        Msg::TrayQuit => "Beenden",
+        Msg::TrayTooltip => "Daily Notes – {count} Notizen heute",
+        Msg::MainWindowTitle => "Daily Notes",
+        Msg::QuickCaptureWindowTitle => "Quick Capture",
+        Msg::SettingsWindowTitle => "Einstellungen - Daily App",
+        Msg::StandupHeading => "Standup vom {date}",
+        Msg::ReminderStandupTitle => "Zeit fürs Standup",
+        Msg::ReminderStandupBody => "Notiere kurz, was heute ansteht.",
+        Msg::ReminderEndOfDayTitle => "Feierabend?",
+        Msg::ReminderEndOfDayBody => "Halte noch fest, was du heute geschafft hast.",
+        Msg::ReminderNoteTitle => "Erinnerung",
+        Msg::ReminderOpen => "Notiz schreiben",
+        Msg::TrayCopySprintSummary => "Sprint-Zusammenfassung kopieren",
+        Msg::SummaryHeading => "Zusammenfassung {from} – {to}",
+        Msg::SummaryTotal => "{count} Notizen",
+        Msg::SummaryByDay => "Nach Tag",
+        Msg::SummaryByTag => "Nach Schlagwort",
+        Msg::SummaryBySection => "Nach Abschnitt",
+        Msg::SummaryDoneTasks => "Erledigte Aufgaben",
+        Msg::SummaryRecurringBlockers => "Wiederkehrende Blocker",
+        Msg::SummaryUntagged => "Ohne Schlagwort",
+        Msg::SectionDone => "Erledigt",
+        Msg::SectionTodo => "Geplant",
+        Msg::SectionBlocker => "Blocker",
+        Msg::ClipboardImage => "Bild aus der Zwischenablage",
+    }
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::TrayNoNotesToday => "No notes today",
+        Msg::TrayCopyStandup => "Copy today's standup",
+        Msg::TrayQuickCapture => "New quick capture",
+        Msg::TrayRecentDays => "Recent days",
+        Msg::TrayNoRecentDays => "No entries",
+        Msg::TrayShowNotes => "Show notes",
+        Msg::TraySettings => "Settings",
+        Msg::TrayQuit => "Quit",
+        Msg::TrayTooltip => "Daily Notes – {count} notes today",
+        Msg::MainWindowTitle => "Daily Notes",
+        Msg::QuickCaptureWindowTitle => "Quick Capture",
+        Msg::SettingsWindowTitle => "Settings - Daily App",
+        Msg::StandupHeading => "Standup for {date}",
+        Msg::ReminderStandupTitle => "Time for your standup",
+        Msg::ReminderStandupBody => "Jot down what's on for today.",
+        Msg::ReminderEndOfDayTitle => "Wrapping up?",
+        Msg::ReminderEndOfDayBody => "Log what you got done today before you leave.",
+        Msg::ReminderNoteTitle => "Reminder",
+        Msg::ReminderOpen => "Write note",
+        Msg::TrayCopySprintSummary => "Copy sprint summary",
+        Msg::SummaryHeading => "Summary {from} – {to}",
+        Msg::SummaryTotal => "{count} notes",
+        Msg::SummaryByDay => "By day",
+        Msg::SummaryByTag => "By tag",
+        Msg::SummaryBySection => "By section",
+        Msg::SummaryDoneTasks => "Done tasks",
+        Msg::SummaryRecurringBlockers => "Recurring blockers",
+        Msg::SummaryUntagged => "Untagged",
+        Msg::SectionDone => "Done",
+        Msg::SectionTodo => "To do",
+        Msg::SectionBlocker => "Blockers",
+        Msg::ClipboardImage => "Image from the clipboard",
+    }
+}
+
+pub fn t(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::De => de(msg),
+        Locale::En => en(msg),
+    }
+}
+
+/// Looks up `msg` and substitutes `{name}` placeholders.
+pub fn t_with(locale: Locale, msg: Msg, args: &[(&str, &str)]) -> String {
+    let mut text = t(locale, msg).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+fn weekday_short(locale: Locale, day: NaiveDate) -> &'static str {
+    const DE: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+    const EN: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    let index = day.weekday().num_days_from_monday() as usize;
+    match locale {
+        Locale::De => DE[index],
+        Locale::En => EN[index],
+    }
+}
+
+/// Full date, e.g. `19.10.2026` or `10/19/2026`.
+pub fn format_date(locale: Locale, day: NaiveDate) -> String {
+    match locale {
+        Locale::De => day.format("%d.%m.%Y").to_string(),
+        Locale::En => day.format("%m/%d/%Y").to_string(),
+    }
+}
+
+/// Compact day label with weekday, e.g. `Mo, 19.10.` or `Mon, 10/19`.
+pub fn format_day_label(locale: Locale, day: NaiveDate) -> String {
+    let date = match locale {
+        Locale::De => day.format("%d.%m.").to_string(),
+        Locale::En => day.format("%m/%d").to_string(),
+    };
+    format!("{}, {}", weekday_short(locale, day), date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Msg; 32] = [
+        Msg::TrayNoNotesToday,
+        Msg::TrayCopyStandup,
+        Msg::TrayQuickCapture,
+        Msg::TrayRecentDays,
+        Msg::TrayNoRecentDays,
+        Msg::TrayShowNotes,
+        Msg::TraySettings,
+        Msg::TrayQuit,
+        Msg::TrayTooltip,
+        Msg::MainWindowTitle,
+        Msg::QuickCaptureWindowTitle,
+        Msg::SettingsWindowTitle,
+        Msg::StandupHeading,
+        Msg::ReminderStandupTitle,
+        Msg::ReminderStandupBody,
+        Msg::ReminderEndOfDayTitle,
+        Msg::ReminderEndOfDayBody,
+        Msg::ReminderNoteTitle,
+        Msg::ReminderOpen,
+        Msg::TrayCopySprintSummary,
+        Msg::SummaryHeading,
+        Msg::SummaryTotal,
+        Msg::SummaryByDay,
+        Msg::SummaryByTag,
+        Msg::SummaryBySection,
+        Msg::SummaryDoneTasks,
+        Msg::SummaryRecurringBlockers,
+        Msg::SummaryUntagged,
+        Msg::SectionDone,
+        Msg::SectionTodo,
+        Msg::SectionBlocker,
+        Msg::ClipboardImage,
+    ];
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn os_tags_map_to_shipped_locales() {
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("de_AT.UTF-8"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("EN"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("en-GB"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr-FR"), None);
+        assert_eq!(Locale::from_tag(""), None);
+    }
+
+    #[test]
+    fn the_settings_override_wins() {
+        assert_eq!(resolve(Some(Locale::De)), Locale::De);
+        assert_eq!(resolve(Some(Locale::En)), Locale::En);
+    }
+
+    #[test]
+    fn both_catalogs_translate_every_message_with_the_same_placeholders() {
+        for msg in ALL {
+            let (de, en) = (t(Locale::De, msg), t(Locale::En, msg));
+            assert!(!de.is_empty() && !en.is_empty(), "{:?}", msg);
+            assert_eq!(placeholders(de), placeholders(en), "{:?}", msg);
+        }
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        assert_eq!(
+            t_with(
+                Locale::En,
+                Msg::SummaryHeading,
+                &[("from", "a"), ("to", "b")]
+            ),
+            "Summary a – b"
+        );
+        assert_eq!(
+            t_with(Locale::De, Msg::TrayTooltip, &[("count", "3")]),
+            "Daily Notes – 3 Notizen heute"
+        );
+        // Unknown names are ignored, missing ones stay visible
+        assert_eq!(
+            t_with(Locale::En, Msg::SummaryTotal, &[("other", "x")]),
+            "{count} notes"
+        );
+    }
+
+    #[test]
+    fn dates_follow_the_locale() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(format_date(Locale::De, day), "19.10.2026");
+        assert_eq!(format_date(Locale::En, day), "10/19/2026");
+        assert_eq!(format_day_label(Locale::De, day), "Mo, 19.10.");
+        assert_eq!(format_day_label(Locale::En, day), "Mon, 10/19");
+    }
+}
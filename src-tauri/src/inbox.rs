@@ -0,0 +1,220 @@
+// Quick captures wait in the inbox until they are filed into a day, turned
+// into a task or discarded. Captures nobody looked at can be filed into the
+// day they were written after a configurable number of hours.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+use crate::days;
+use crate::history::{Change, Dependents};
+use crate::notes::{self, NoteEntry, Section, NOTE_COLUMNS};
+use crate::tasks::TaskState;
+
+const IN_INBOX: &str = "is_quick_capture = 1 AND processed_at IS NULL AND archived_at IS NULL \
+     AND TRIM(content) != ''";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum InboxAction {
+    /// Into `day`, the day it was written without one. A section replaces
+    /// the one typed into the capture.
+    File {
+        day: Option<NaiveDate>,
+        section: Option<Section>,
+    },
+    Task,
+    Discard,
+}
+
+/// Unprocessed quick captures, oldest first.
+pub async fn list(pool: &SqlitePool) -> Result<Vec<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE {} ORDER BY created_at ASC, id ASC",
+        NOTE_COLUMNS, IN_INBOX
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch inbox: {}", e))
+}
+
+/// Takes capture `id` out of the inbox. Returns the changes for the history,
+/// including notes whose links to a discarded capture were rewritten.
+pub async fn process(
+    pool: &SqlitePool,
+    id: i64,
+    action: &InboxAction,
+) -> Result<Vec<Change>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let note = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE id = ?1 AND {}",
+        NOTE_COLUMNS, IN_INBOX
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch note: {}", e))?
+    .ok_or_else(|| format!("Note {} is not in the inbox", id))?;
+    let now = notes::timestamp(&Utc::now());
+    let changes = match processed(&note, action, &now)? {
+        Some(after) => {
+            notes::restore(&mut tx, id, Some(&after)).await?;
+            vec![Change::updated(&note, &after)]
+        }
+        None => {
+            let mut changes: Vec<Change> = notes::unlink(&mut tx, &note, &now)
+                .await?
+                .iter()
+                .map(|(before, after)| Change::updated(before, after))
+                .collect();
+            let dependents = Dependents::load(&mut tx, id).await?;
+            notes::restore(&mut tx, id, None).await?;
+            changes.push(Change::deleted(&note, dependents));
+            changes
+        }
+    };
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit inbox change: {}", e))?;
+    Ok(changes)
+}
+
+/// Files every capture written before `cutoff` into its own day. Returns
+/// the notes before and after.
+pub async fn auto_file(
+    pool: &SqlitePool,
+    cutoff: &DateTime<Utc>,
+) -> Result<Vec<(NoteEntry, NoteEntry)>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let stale = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE {} AND created_at < ?1",
+        NOTE_COLUMNS, IN_INBOX
+    ))
+    .bind(notes::timestamp(cutoff))
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch inbox: {}", e))?;
+
+    let now = notes::timestamp(&Utc::now());
+    let file = InboxAction::File {
+        day: None,
+        section: None,
+    };
+    let mut filed = Vec::new();
+    for note in stale {
+        if let Some(after) = processed(&note, &file, &now)? {
+            notes::restore(&mut tx, note.id, Some(&after)).await?;
+            filed.push((note, after));
+        }
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit inbox change: {}", e))?;
+    Ok(filed)
+}
+
+fn processed(
+    note: &NoteEntry,
+    action: &InboxAction,
+    now: &str,
+) -> Result<Option<NoteEntry>, String> {
+    let mut after = note.clone();
+    match action {
+        InboxAction::File { day, section } => {
+            if let Some(day) = day {
+                let at = days::same_time_on(&note.created_at, *day)
+                    .ok_or_else(|| format!("Note {} has an invalid date", note.id))?;
+                after.created_at = notes::timestamp(&at);
+            }
+            if section.is_some() {
+                after.section = *section;
+            }
+        }
+        InboxAction::Task => {
+            if after.task_state.is_none() {
+                after.task_state = Some(TaskState::Open);
+            }
+        }
+        InboxAction::Discard => return Ok(None),
+    }
+    after.processed_at = Some(now.to_string());
+    after.updated_at = now.to_string();
+    Ok(Some(after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-10-19T12:00:00.000Z";
+
+    fn capture(section: Option<Section>) -> NoteEntry {
+        NoteEntry {
+            id: 1,
+            content: "Ask Jana about the release".to_string(),
+            created_at: "2026-10-19T08:00:00.000Z".to_string(),
+            updated_at: "2026-10-19T08:00:00.000Z".to_string(),
+            is_quick_capture: true,
+            section,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state: None,
+            completed_at: None,
+            processed_at: None,
+            pin_order: None,
+            starred: false,
+            archived_at: None,
+        }
+    }
+
+    fn file(day: Option<NaiveDate>, section: Option<Section>) -> InboxAction {
+        InboxAction::File { day, section }
+    }
+
+    #[test]
+    fn filing_keeps_the_capture_unless_told_otherwise() {
+        let note = capture(Some(Section::Blocker));
+        let after = processed(&note, &file(None, None), NOW).unwrap().unwrap();
+        assert_eq!(after.created_at, note.created_at);
+        assert_eq!(after.section, Some(Section::Blocker));
+        assert_eq!(after.processed_at.as_deref(), Some(NOW));
+        assert!(after.is_quick_capture);
+
+        let after = processed(&note, &file(None, Some(Section::Todo)), NOW)
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.section, Some(Section::Todo));
+    }
+
+    #[test]
+    fn filing_into_another_day_keeps_the_time() {
+        let note = capture(None);
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let after = processed(&note, &file(Some(day), None), NOW)
+            .unwrap()
+            .unwrap();
+        let expected = days::same_time_on(&note.created_at, day).unwrap();
+        assert_eq!(after.created_at, notes::timestamp(&expected));
+        assert_eq!(days::local_day_of(&after.created_at), Some(day));
+    }
+
+    #[test]
+    fn tasks_open_and_discards_remove() {
+        let after = processed(&capture(None), &InboxAction::Task, NOW)
+            .unwrap()
+            .unwrap();
+        assert_eq!(after.task_state, Some(TaskState::Open));
+        assert_eq!(after.processed_at.as_deref(), Some(NOW));
+
+        assert!(processed(&capture(None), &InboxAction::Discard, NOW)
+            .unwrap()
+            .is_none());
+    }
+}
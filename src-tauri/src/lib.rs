@@ -1,181 +1,852 @@
-use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Manager, Window, WindowEvent
-};
-use tauri_plugin_positioner::{Position, WindowExt};
-use tauri_plugin_sql::{Migration, MigrationKind};
+mod archive;
+mod attachments;
+mod autostart;
+mod bulk;
+mod calendar;
+mod capture;
+mod clipboard;
+// Public for the command line tool in src/bin/
+#[doc(hidden)]
+pub mod days;
+// Public for the benchmarks in benches/
+#[doc(hidden)]
+pub mod db;
+mod events;
+mod history;
+mod i18n;
+mod inbox;
+mod links;
+#[doc(hidden)]
+pub mod natural_date;
+#[doc(hidden)]
+pub mod notes;
+mod pins;
+mod placement;
+mod reminders;
+mod report;
+mod retention;
+mod rollover;
+mod scheduler;
+mod settings;
+mod stats;
+#[doc(hidden)]
+pub mod tasks;
+mod tray;
+mod window_state;
+mod windows;
 
-// Simplified commands
+use chrono::{Local, NaiveDate, Utc};
+use events::ChangeKind;
+use history::{Change, Direction, Operation};
+use natural_date::When;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Listener, Manager, Window, WindowEvent};
+
+// Lets the quick capture window show what a line will be saved as
 #[tauri::command]
-async fn get_today_note() -> Result<String, String> {
-    Ok("".to_string())
+fn preview_capture(input: String) -> capture::ParsedCapture {
+    capture::parse(&input, Local::now().naive_local())
 }
 
 #[tauri::command]
-async fn save_today_note(_content: String) -> Result<(), String> {
+async fn save_capture(app: AppHandle, input: String) -> Result<notes::NoteEntry, String> {
+    let parsed = capture::parse(&input, Local::now().naive_local());
+    let (task_state, content) = tasks::from_markdown(&parsed.content);
+    if content.is_empty() {
+        return Err("Note is empty".to_string());
+    }
+    // `/todo` captures are open tasks
+    let task_state = task_state.or_else(|| {
+        (parsed.section == Some(notes::Section::Todo)).then_some(tasks::TaskState::Open)
+    });
+    let created_at = parsed.when().map(When::to_utc).unwrap_or_else(Utc::now);
+    let pool = db::pool(&app).await?;
+    let note = notes::insert_note(
+        &pool,
+        &notes::NewNote {
+            content: content.to_string(),
+            created_at,
+            is_quick_capture: true,
+            section: parsed.section,
+            priority: parsed.priority,
+            duration_minutes: parsed.duration_minutes,
+            tags: parsed.tags,
+            task_state,
+        },
+    )
+    .await?;
+    record(&app, Operation::quick_capture(&note));
+    events::notes_changed(&app, ChangeKind::Created, &[&note]);
+    Ok(note)
+}
+
+// Clip waiting for confirmation in the quick capture window
+type PendingClip = std::sync::Mutex<Option<clipboard::Clip>>;
+
+fn read_clipboard(app: &AppHandle) -> Result<clipboard::Clip, String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let text = app.clipboard().read_text().unwrap_or_default();
+    if let Some(clip) = clipboard::Clip::from_text(&text) {
+        return Ok(clip);
+    }
+    let image = app
+        .clipboard()
+        .read_image()
+        .map_err(|_| "Clipboard is empty".to_string())?;
+    clipboard::Clip::from_rgba(image.width(), image.height(), image.rgba())
+}
+
+// Saves the clipboard as a quick capture, after confirmation unless silent
+async fn capture_clipboard(app: &AppHandle) -> Result<(), String> {
+    let clip = read_clipboard(app)?;
+    if settings::current(app).clipboard_capture_silent {
+        store_clip(app, &clip).await?;
+        return Ok(());
+    }
+    let preview = clip.preview();
+    *app.state::<PendingClip>()
+        .lock()
+        .map_err(|e| format!("Clipboard lock poisoned: {}", e))? = Some(clip);
+    windows::show_quick_capture(app);
+    events::clipboard_capture(app, preview);
     Ok(())
 }
 
-async fn create_tray_menu(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let show_notes = MenuItemBuilder::new("Notizen anzeigen").id("show_notes").build(app)?;
-    let settings = MenuItemBuilder::new("Einstellungen").id("settings").build(app)?;
-    let quit = MenuItemBuilder::new("Beenden").id("quit").build(app)?;
-
-    let menu = MenuBuilder::new(app)
-        .items(&[&show_notes, &settings, &quit])
-        .build()?;
-
-    // Tray icon creation with proper positioner integration according to Context7
-    let _tray = TrayIconBuilder::new()
-        .menu(&menu)
-        .show_menu_on_left_click(false)
-        .icon(app.default_window_icon().unwrap().clone())
-        .on_tray_icon_event(|app, event| {
-            // CRITICAL: Handle tray events for positioner plugin FIRST
-            tauri_plugin_positioner::on_tray_event(app.app_handle(), &event);
-            
-            // Handle click events
-            if let TrayIconEvent::Click { button, button_state, .. } = event {
-                if button == MouseButton::Left && button_state == MouseButtonState::Up {
-                    if let Some(window) = app.app_handle().get_webview_window("main") {
-                        if window.is_visible().unwrap_or(false) {
-                            let _ = window.hide();
-                        } else {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                            
-                            // Try TrayCenter but with error handling
-                            if let Err(_) = window.move_window(Position::TrayCenter) {
-                                // Fallback to TopRight if TrayCenter fails
-                                let _ = window.move_window(Position::TopRight);
-                            }
-                        }
+async fn store_clip(app: &AppHandle, clip: &clipboard::Clip) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(app).await?;
+    let label = i18n::t(settings::locale(app), i18n::Msg::ClipboardImage);
+    let note = clipboard::save(&pool, &db::attachments_dir(app)?, clip, label).await?;
+    record(app, Operation::quick_capture(&note));
+    events::notes_changed(app, ChangeKind::Created, &[&note]);
+    if let clipboard::Clip::Url(url) = clip {
+        if settings::current(app).fetch_link_titles && clipboard::may_fetch_title(url) {
+            fetch_link_title(app, note.id, url.clone());
+        }
+    }
+    Ok(note)
+}
+
+// Puts the page title into a captured link once it has been fetched
+fn fetch_link_title(app: &AppHandle, id: i64, url: String) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let result = clipboard::fetch_title(&url).and_then(|title| {
+            let Some(title) = title else {
+                return Ok(());
+            };
+            tauri::async_runtime::block_on(async {
+                let pool = db::pool(&app).await?;
+                if let Some(change) = clipboard::apply_title(&pool, id, &url, &title).await? {
+                    emit_changes(&app, std::slice::from_ref(&change), Direction::Redo);
+                    if let Ok(mut history) = app.state::<history::HistoryState>().lock() {
+                        history.record_follow_up(change);
                     }
                 }
+                Ok(())
+            })
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to fetch link title: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+fn get_pending_clip(app: AppHandle) -> Result<Option<clipboard::ClipPreview>, String> {
+    let pending = app.state::<PendingClip>();
+    let pending = pending
+        .lock()
+        .map_err(|e| format!("Clipboard lock poisoned: {}", e))?;
+    Ok(pending.as_ref().map(clipboard::Clip::preview))
+}
+
+#[tauri::command]
+async fn save_pending_clip(app: AppHandle) -> Result<notes::NoteEntry, String> {
+    let clip = app
+        .state::<PendingClip>()
+        .lock()
+        .map_err(|e| format!("Clipboard lock poisoned: {}", e))?
+        .take()
+        .ok_or_else(|| "Nothing to capture".to_string())?;
+    store_clip(&app, &clip).await
+}
+
+#[tauri::command]
+fn discard_pending_clip(app: AppHandle) -> Result<(), String> {
+    app.state::<PendingClip>()
+        .lock()
+        .map_err(|e| format!("Clipboard lock poisoned: {}", e))?
+        .take();
+    Ok(())
+}
+
+fn record(app: &AppHandle, operation: Operation) {
+    if let Ok(mut history) = app.state::<history::HistoryState>().lock() {
+        history.record(operation);
+    }
+}
+
+// One event for the whole operation, which can create, update and delete
+// notes at once. `Redo` reports the changes as they were made.
+fn emit_changes(app: &AppHandle, changes: &[Change], direction: Direction) {
+    let mut kinds: Vec<ChangeKind> = Vec::new();
+    let mut changed: Vec<&notes::NoteEntry> = Vec::new();
+    for change in changes {
+        let (current, target) = change.states(direction);
+        let kind = match (current, target) {
+            (_, None) => ChangeKind::Deleted,
+            (None, Some(_)) => ChangeKind::Created,
+            _ => ChangeKind::Updated,
+        };
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+        changed.extend(current.into_iter().chain(target));
+    }
+    if let Some(kind) = ChangeKind::combined(&kinds) {
+        events::notes_changed(app, kind, &changed);
+    }
+}
+
+// Steps the latest operation, or only the latest quick capture, back or forth
+async fn step_history(app: &AppHandle, direction: Direction, last_capture: bool) -> Result<bool, String> {
+    let operation = {
+        let state = app.state::<history::HistoryState>();
+        let mut history = state
+            .lock()
+            .map_err(|e| format!("History lock poisoned: {}", e))?;
+        if last_capture {
+            history.pop_last_capture()
+        } else {
+            history.pop(direction)
+        }
+    };
+    let Some(mut operation) = operation else {
+        return Ok(false);
+    };
+    let (taken_from, put_on) = match direction {
+        Direction::Undo => (Direction::Undo, Direction::Redo),
+        Direction::Redo => (Direction::Redo, Direction::Undo),
+    };
+
+    let applied = match db::pool(app).await {
+        Ok(pool) => history::apply(&pool, &mut operation, direction).await,
+        Err(e) => Err(e),
+    };
+    match applied {
+        Ok(true) => {}
+        // Changed since, by something outside the history. Dropped, since
+        // stepping it would overwrite that change.
+        Ok(false) => return Err("The note was changed in the meantime".to_string()),
+        Err(e) => {
+            if let Ok(mut history) = app.state::<history::HistoryState>().lock() {
+                history.push(taken_from, operation);
             }
+            return Err(e);
+        }
+    }
+
+    emit_changes(app, &operation.changes, direction);
+
+    if let Ok(mut history) = app.state::<history::HistoryState>().lock() {
+        history.push(put_on, operation);
+    }
+    Ok(true)
+}
+
+// Both return false when there is nothing to undo or redo
+#[tauri::command]
+async fn undo(app: AppHandle) -> Result<bool, String> {
+    step_history(&app, Direction::Undo, false).await
+}
+
+#[tauri::command]
+async fn redo(app: AppHandle) -> Result<bool, String> {
+    step_history(&app, Direction::Redo, false).await
+}
+
+#[tauri::command]
+async fn undo_last_capture(app: AppHandle) -> Result<bool, String> {
+    step_history(&app, Direction::Undo, true).await
+}
+
+#[derive(Deserialize)]
+struct CreateNoteRequest {
+    content: String,
+    #[serde(default)]
+    is_quick_capture: bool,
+    // RFC 3339 or natural language like "gestern 16 Uhr", defaults to now
+    created_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateNoteRequest {
+    id: i64,
+    content: String,
+    created_at: Option<String>,
+}
+
+#[tauri::command]
+async fn create_note(app: AppHandle, request: CreateNoteRequest) -> Result<notes::NoteEntry, String> {
+    let created_at = match request.created_at.as_deref() {
+        Some(input) => natural_date::resolve(input)?,
+        None => Utc::now(),
+    };
+    let (task_state, content) = tasks::from_markdown(&request.content);
+    let pool = db::pool(&app).await?;
+    let note = notes::insert_note(
+        &pool,
+        &notes::NewNote {
+            content: content.trim().to_string(),
+            created_at,
+            is_quick_capture: request.is_quick_capture,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state,
+        },
+    )
+    .await?;
+    record(&app, Operation::new(vec![Change::created(&note)]));
+    events::notes_changed(&app, ChangeKind::Created, &[&note]);
+    Ok(note)
+}
+
+#[tauri::command]
+async fn update_note(app: AppHandle, request: UpdateNoteRequest) -> Result<notes::NoteEntry, String> {
+    let created_at = request
+        .created_at
+        .as_deref()
+        .map(natural_date::resolve)
+        .transpose()?;
+    let (task_state, content) = tasks::from_markdown(&request.content);
+    let pool = db::pool(&app).await?;
+    // Kept to report the old day as well when the note is moved
+    let before = notes::get_note(&pool, request.id).await?;
+    let mut note = notes::update_note(&pool, request.id, content.trim(), created_at).await?;
+    if task_state.is_some() {
+        note = tasks::set_state(&pool, note.id, task_state).await?;
+    }
+    if let Some(before) = &before {
+        record(&app, Operation::new(vec![Change::updated(before, &note)]));
+    }
+    let changed: Vec<&notes::NoteEntry> = before.iter().chain([&note]).collect();
+    events::notes_changed(&app, ChangeKind::Updated, &changed);
+    Ok(note)
+}
+
+#[tauri::command]
+async fn delete_note(app: AppHandle, id: i64) -> Result<(), String> {
+    let pool = db::pool(&app).await?;
+    // Undoing the deletion also restores the links that were rewritten for it
+    let changes = notes::delete_note(&pool, id).await?;
+    emit_changes(&app, &changes, Direction::Redo);
+    record(&app, Operation::new(changes));
+    Ok(())
+}
+
+// Retags, moves, archives, deletes or merges every note matching `filter`
+// at once
+#[tauri::command]
+async fn bulk_update_notes(
+    app: AppHandle,
+    filter: bulk::NoteFilter,
+    action: bulk::BulkAction,
+) -> Result<bulk::BulkSummary, String> {
+    let pool = db::pool(&app).await?;
+    let result = bulk::apply(&pool, &filter, &action).await?;
+    if result.changes.is_empty() {
+        return Ok(result.summary);
+    }
+    // Deletes and merges also update the notes that linked to the removed ones
+    emit_changes(&app, &result.changes, Direction::Redo);
+    record(&app, Operation::new(result.changes));
+    Ok(result.summary)
+}
+
+// Quick captures that have not been filed yet, oldest first
+#[tauri::command]
+async fn get_inbox(app: AppHandle) -> Result<Vec<notes::NoteEntry>, String> {
+    let pool = db::pool(&app).await?;
+    inbox::list(&pool).await
+}
+
+async fn process_capture(app: &AppHandle, id: i64, action: inbox::InboxAction) -> Result<(), String> {
+    let pool = db::pool(app).await?;
+    let changes = inbox::process(&pool, id, &action).await?;
+    emit_changes(app, &changes, Direction::Redo);
+    record(app, Operation::new(changes));
+    Ok(())
+}
+
+// Without a day the capture stays on the day it was written
+#[tauri::command]
+async fn file_capture(
+    app: AppHandle,
+    id: i64,
+    day: Option<NaiveDate>,
+    section: Option<notes::Section>,
+) -> Result<(), String> {
+    process_capture(&app, id, inbox::InboxAction::File { day, section }).await
+}
+
+#[tauri::command]
+async fn capture_to_task(app: AppHandle, id: i64) -> Result<(), String> {
+    process_capture(&app, id, inbox::InboxAction::Task).await
+}
+
+#[tauri::command]
+async fn discard_capture(app: AppHandle, id: i64) -> Result<(), String> {
+    process_capture(&app, id, inbox::InboxAction::Discard).await
+}
+
+// Notes of the configured recent window, newest first, for the quick capture
+// window
+#[tauri::command]
+async fn get_recent_notes(app: AppHandle) -> Result<Vec<notes::NoteEntry>, String> {
+    let retention = settings::current(&app).capture_retention;
+    let pool = db::pool(&app).await?;
+    notes::recent_notes(&pool, &retention.visible_since(&Utc::now()), retention.visible_limit).await
+}
+
+// What saving `retention` would show and archive or delete right now
+#[tauri::command]
+async fn preview_capture_retention(
+    app: AppHandle,
+    retention: retention::CaptureRetention,
+) -> Result<retention::RetentionPreview, String> {
+    retention.validate()?;
+    let pool = db::pool(&app).await?;
+    retention::preview(&pool, &retention, &Utc::now()).await
+}
+
+// Archived notes leave the default listings but stay searchable
+#[tauri::command]
+async fn archive_note(app: AppHandle, id: i64) -> Result<notes::NoteEntry, String> {
+    set_archived(&app, id, true).await
+}
+
+#[tauri::command]
+async fn unarchive_note(app: AppHandle, id: i64) -> Result<notes::NoteEntry, String> {
+    set_archived(&app, id, false).await
+}
+
+async fn set_archived(app: &AppHandle, id: i64, archived: bool) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(app).await?;
+    let (before, after) = archive::set_archived(&pool, id, archived, &Utc::now()).await?;
+    if before.archived_at != after.archived_at {
+        record(app, Operation::new(vec![Change::updated(&before, &after)]));
+        events::notes_changed(app, ChangeKind::Updated, &[&after]);
+    }
+    Ok(after)
+}
+
+// How many notes saving `policy` would archive right now
+#[tauri::command]
+async fn preview_archive_policy(app: AppHandle, policy: archive::ArchivePolicy) -> Result<usize, String> {
+    policy.validate()?;
+    let pool = db::pool(&app).await?;
+    archive::preview(&pool, &policy, &Utc::now()).await
+}
+
+// Searches archived and active notes, `archived` narrows it to either
+#[tauri::command]
+async fn search_notes(
+    app: AppHandle,
+    query: String,
+    archived: Option<bool>,
+) -> Result<Vec<notes::NoteEntry>, String> {
+    let pool = db::pool(&app).await?;
+    notes::search(&pool, &query, archived).await
+}
+
+#[tauri::command]
+async fn toggle_pin(app: AppHandle, id: i64) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(&app).await?;
+    let before = notes::get_note(&pool, id).await?;
+    let note = pins::toggle_pin(&pool, id).await?;
+    if let Some(before) = &before {
+        record(&app, Operation::new(vec![Change::updated(before, &note)]));
+    }
+    events::notes_changed(&app, ChangeKind::Updated, &[&note]);
+    Ok(note)
+}
+
+#[tauri::command]
+async fn toggle_star(app: AppHandle, id: i64) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(&app).await?;
+    let before = notes::get_note(&pool, id).await?;
+    let note = pins::toggle_star(&pool, id).await?;
+    if let Some(before) = &before {
+        record(&app, Operation::new(vec![Change::updated(before, &note)]));
+    }
+    events::notes_changed(&app, ChangeKind::Updated, &[&note]);
+    Ok(note)
+}
+
+// `ids` move to the top of the pinned list in this order, the rest follow
+#[tauri::command]
+async fn reorder_pinned(app: AppHandle, ids: Vec<i64>) -> Result<Vec<notes::NoteEntry>, String> {
+    let pool = db::pool(&app).await?;
+    let before = notes::pinned(&pool).await?;
+    let pinned = pins::reorder(&pool, &ids).await?;
+    let changes: Vec<Change> = before
+        .iter()
+        .filter_map(|old| {
+            let new = pinned.iter().find(|n| n.id == old.id)?;
+            (new.pin_order != old.pin_order).then(|| Change::updated(old, new))
         })
-        .on_menu_event(move |app, event| match event.id().as_ref() {
-            "show_notes" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                    
-                    // Try TrayCenter with fallback to TopRight
-                    if let Err(_) = window.move_window(Position::TrayCenter) {
-                        let _ = window.move_window(Position::TopRight);
-                    }
-                }
-            }
-            "settings" => {
-                if let Some(window) = app.get_webview_window("settings") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                } else {
-                    let _ = tauri::WebviewWindowBuilder::new(
-                        app,
-                        "settings",
-                        tauri::WebviewUrl::App("settings.html".into()),
-                    )
-                    .title("Einstellungen - Daily App")
-                    .inner_size(400.0, 500.0)
-                    .resizable(false)
-                    .maximizable(false)
-                    .minimizable(false)
-                    .center()
-                    .build();
-                }
-            }
-            "quit" => {
-                app.exit(0);
+        .collect();
+    if !changes.is_empty() {
+        let changed: Vec<&notes::NoteEntry> = changes.iter().filter_map(|c| c.after.as_ref()).collect();
+        events::notes_changed(&app, ChangeKind::Updated, &changed);
+        record(&app, Operation::new(changes));
+    }
+    Ok(pinned)
+}
+
+#[tauri::command]
+async fn toggle_task(app: AppHandle, id: i64) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(&app).await?;
+    let before = notes::get_note(&pool, id).await?;
+    let note = tasks::toggle(&pool, id).await?;
+    if let Some(before) = &before {
+        record(&app, Operation::new(vec![Change::updated(before, &note)]));
+    }
+    events::notes_changed(&app, ChangeKind::Updated, &[&note]);
+    Ok(note)
+}
+
+// `None` turns a task back into a plain note
+#[tauri::command]
+async fn set_task_state(
+    app: AppHandle,
+    id: i64,
+    state: Option<tasks::TaskState>,
+) -> Result<notes::NoteEntry, String> {
+    let pool = db::pool(&app).await?;
+    let before = notes::get_note(&pool, id).await?;
+    let note = tasks::set_state(&pool, id, state).await?;
+    if let Some(before) = &before {
+        record(&app, Operation::new(vec![Change::updated(before, &note)]));
+    }
+    events::notes_changed(&app, ChangeKind::Updated, &[&note]);
+    Ok(note)
+}
+
+#[tauri::command]
+async fn list_open_tasks(app: AppHandle) -> Result<Vec<notes::NoteEntry>, String> {
+    let pool = db::pool(&app).await?;
+    tasks::open_tasks(&pool).await
+}
+
+#[derive(Serialize)]
+struct SummaryResponse {
+    summary: report::Summary,
+    /// Rendered for pasting into retro or review notes
+    text: String,
+}
+
+async fn summary_response(
+    app: &AppHandle,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<SummaryResponse, String> {
+    report::check_range(from, to)?;
+    let pool = db::pool(app).await?;
+    let summary = report::summarize(from, to, &notes::notes_in_range(&pool, from, to).await?);
+    let text = report::render_summary(settings::locale(app), &summary);
+    Ok(SummaryResponse { summary, text })
+}
+
+#[tauri::command]
+async fn get_summary(app: AppHandle, from: NaiveDate, to: NaiveDate) -> Result<SummaryResponse, String> {
+    summary_response(&app, from, to).await
+}
+
+// `back` counts sprints before the current one, 0 or missing is the current sprint
+#[tauri::command]
+async fn get_sprint_summary(app: AppHandle, back: Option<u32>) -> Result<SummaryResponse, String> {
+    let sprint = settings::current(&app).sprint;
+    let (from, to) = sprint.sprint_before(days::today(), back.unwrap_or(0));
+    summary_response(&app, from, to).await
+}
+
+// Keyset paging for the main list, `cursor` comes from a previous page
+#[tauri::command]
+async fn get_notes(
+    app: AppHandle,
+    cursor: Option<String>,
+    direction: Option<notes::PageDirection>,
+    limit: Option<u32>,
+) -> Result<notes::NotePage, String> {
+    let cursor = cursor
+        .as_deref()
+        .map(|token| notes::Cursor::decode(token).ok_or_else(|| "Invalid cursor".to_string()))
+        .transpose()?;
+    let pool = db::pool(&app).await?;
+    notes::list_page(&pool, cursor.as_ref(), direction.unwrap_or_default(), limit).await
+}
+
+#[tauri::command]
+async fn get_stats(app: AppHandle) -> Result<stats::Stats, String> {
+    let key = stats::CacheKey {
+        today: days::today(),
+        day_start_hour: days::day_start_hour(),
+        calendar: settings::current(&app).work_calendar,
+    };
+    if let Ok(cache) = app.state::<stats::StatsCache>().lock() {
+        if let Some((cached_key, cached)) = cache.as_ref() {
+            if *cached_key == key {
+                return Ok(cached.clone());
             }
-            _ => {}
-        })
-        .build(app)?;
+        }
+    }
+
+    let pool = db::pool(&app).await?;
+    let result = stats::load(&pool, key.today, &key.calendar).await?;
+    if let Ok(mut cache) = app.state::<stats::StatsCache>().lock() {
+        *cache = Some((key, result.clone()));
+    }
+    Ok(result)
+}
+
+// Per-day counts for a calendar heatmap of a month, or a year without `month`
+#[tauri::command]
+async fn get_heatmap(
+    app: AppHandle,
+    year: i32,
+    month: Option<u32>,
+) -> Result<Vec<report::DayCount>, String> {
+    let (from, to) = days::period(year, month).ok_or_else(|| "Invalid month".to_string())?;
+    let pool = db::pool(&app).await?;
+    let totals = notes::day_totals(&pool, Some((from, to))).await?;
+    Ok(stats::fill_days(from, to, &totals))
+}
+
+// The notes of a day, today's by default, with the open tasks it carries over
+#[tauri::command]
+async fn get_day(app: AppHandle, date: Option<NaiveDate>) -> Result<notes::DayView, String> {
+    let pool = db::pool(&app).await?;
+    notes::day_view(&pool, date.unwrap_or_else(days::today)).await
+}
+
+// Notes linking to a note via `[[note:id]]` or to a day via `[[YYYY-MM-DD]]`
+#[tauri::command]
+async fn get_backlinks(
+    app: AppHandle,
+    note_id: Option<i64>,
+    day: Option<NaiveDate>,
+) -> Result<Vec<notes::NoteEntry>, String> {
+    let target = match (note_id, day) {
+        (Some(id), None) => links::Link::Note(id),
+        (None, Some(day)) => links::Link::Day(day),
+        _ => return Err("Pass either a note or a day".to_string()),
+    };
+    let pool = db::pool(&app).await?;
+    links::backlinks(&pool, &target).await
+}
+
+// Links to notes that no longer exist
+#[tauri::command]
+async fn get_broken_links(app: AppHandle) -> Result<Vec<links::BrokenLink>, String> {
+    let pool = db::pool(&app).await?;
+    links::broken(&pool).await
+}
+
+// Files the user dropped onto a window. Only these can be attached by path,
+// the webview must not get to read any file the app can access.
+type DroppedFiles = std::sync::Mutex<Vec<std::path::PathBuf>>;
+
+// Attaches a copy of a file dropped onto a note, once
+#[tauri::command]
+async fn attach_file(
+    app: AppHandle,
+    note_id: i64,
+    path: String,
+) -> Result<attachments::Attachment, String> {
+    {
+        let dropped = app.state::<DroppedFiles>();
+        let mut dropped = dropped
+            .lock()
+            .map_err(|e| format!("Dropped files lock poisoned: {}", e))?;
+        let index = dropped
+            .iter()
+            .position(|file| file.as_path() == std::path::Path::new(&path))
+            .ok_or_else(|| format!("{} was not dropped onto the window", path))?;
+        dropped.remove(index);
+    }
+    let pool = db::pool(&app).await?;
+    let root = db::attachments_dir(&app)?;
+    attachments::add_file(&pool, &root, note_id, std::path::Path::new(&path)).await
+}
+
+// Attaches pasted content, e.g. a screenshot from the clipboard
+#[tauri::command]
+async fn attach_bytes(
+    app: AppHandle,
+    note_id: i64,
+    name: String,
+    bytes: Vec<u8>,
+) -> Result<attachments::Attachment, String> {
+    let pool = db::pool(&app).await?;
+    let root = db::attachments_dir(&app)?;
+    attachments::add(&pool, &root, note_id, &name, &bytes).await
+}
+
+#[tauri::command]
+async fn list_attachments(
+    app: AppHandle,
+    note_id: i64,
+) -> Result<Vec<attachments::Attachment>, String> {
+    let pool = db::pool(&app).await?;
+    attachments::for_notes(&pool, &[note_id]).await
+}
 
+// Opens a copy under its original name, so the stored file can't be edited
+#[tauri::command]
+async fn open_attachment(app: AppHandle, id: i64) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let pool = db::pool(&app).await?;
+    let attachment = attachments::get(&pool, id)
+        .await?
+        .ok_or_else(|| "Attachment not found".to_string())?;
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve cache directory: {}", e))?
+        .join("open")
+        .join(&attachment.hash);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let path = dir.join(&attachment.name);
+    let blob = attachments::blob_path(&db::attachments_dir(&app)?, &attachment.hash);
+    std::fs::copy(blob, &path).map_err(|e| format!("Failed to copy attachment: {}", e))?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open attachment: {}", e))
+}
+
+#[tauri::command]
+async fn delete_attachment(app: AppHandle, id: i64) -> Result<(), String> {
+    let pool = db::pool(&app).await?;
+    attachments::delete(&pool, id).await?;
+    if let Err(e) = rollover::collect_attachment_garbage(&app).await {
+        eprintln!("Failed to clean up attachments: {}", e);
+    }
+    Ok(())
+}
+
+// Writes the standup of a day and its attachments into the downloads
+// folder. Returns the folder.
+#[tauri::command]
+async fn export_day(app: AppHandle, date: NaiveDate) -> Result<String, String> {
+    let pool = db::pool(&app).await?;
+    // What the day shows, today with pinned notes and carried over tasks
+    let day_notes = notes::day_view(&pool, date).await?.into_entries();
+    let ids: Vec<i64> = day_notes.iter().map(|n| n.id).collect();
+    let attached = attachments::for_notes(&pool, &ids).await?;
+
+    let dir = app
+        .path()
+        .download_dir()
+        .map_err(|e| format!("Failed to resolve downloads folder: {}", e))?
+        .join(format!("daily-{}", date.format("%Y-%m-%d")));
+    let files = dir.join("attachments");
+    std::fs::create_dir_all(&files).map_err(|e| format!("Failed to create export folder: {}", e))?;
+    let root = db::attachments_dir(&app)?;
+    for attachment in &attached {
+        std::fs::copy(
+            attachments::blob_path(&root, &attachment.hash),
+            files.join(attachment.export_name()),
+        )
+        .map_err(|e| format!("Failed to export {}: {}", attachment.name, e))?;
+    }
+    let text = report::standup(settings::locale(&app), date, &day_notes, &attached);
+    std::fs::write(dir.join("standup.md"), text)
+        .map_err(|e| format!("Failed to write standup: {}", e))?;
+    Ok(dir.to_string_lossy().into_owned())
+}
+
+// Resolves a date typed by the user so the UI can show it before saving
+#[tauri::command]
+fn resolve_date(input: String) -> Result<String, String> {
+    natural_date::resolve(&input).map(|dt| notes::timestamp(&dt))
+}
+
+// Simplified commands
+#[tauri::command]
+async fn get_today_note() -> Result<String, String> {
+    Ok("".to_string())
+}
+
+#[tauri::command]
+async fn save_today_note(_content: String) -> Result<(), String> {
     Ok(())
 }
 
 fn handle_window_event(window: &Window<tauri::Wry>, event: &WindowEvent) {
+    window_state::track(window, event);
+
     match event {
         WindowEvent::CloseRequested { api, .. } => {
             window.hide().unwrap();
             api.prevent_close();
         }
+        // Handled on the event loop before the webview can ask to attach them
+        WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+            if let Ok(mut dropped) = window.state::<DroppedFiles>().lock() {
+                *dropped = paths.clone();
+            }
+        }
         _ => {}
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let migrations = vec![
-        Migration {
-            version: 1,
-            description: "create_notes_table",
-            sql: "CREATE TABLE IF NOT EXISTS notes (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                content TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                is_quick_capture BOOLEAN NOT NULL DEFAULT 0
-            );",
-            kind: MigrationKind::Up,
-        },
-        Migration {
-            version: 2,
-            description: "create_notes_index",
-            sql: "CREATE INDEX IF NOT EXISTS idx_notes_created_at ON notes(created_at);",
-            kind: MigrationKind::Up,
-        },
-    ];
-
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_positioner::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            None,
+        ))
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
-                .with_shortcuts(["CommandOrControl+Shift+N", "CommandOrControl+Shift+Space"])?
+                .with_shortcuts(["CommandOrControl+Shift+N", "CommandOrControl+Shift+Space", "CommandOrControl+Alt+Z", "CommandOrControl+Alt+V"])?
                 .with_handler(|app, shortcut, event| {
                     if event.state == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-                        if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::KeyN) || 
+                        if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::KeyN) ||
                            shortcut.matches(tauri_plugin_global_shortcut::Modifiers::SUPER | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::KeyN) {
                             // Main window toggle
-                            if let Some(window) = app.get_webview_window("main") {
-                                if window.is_visible().unwrap_or(false) {
-                                    let _ = window.hide();
-                                } else {
-                                    let _ = window.show();
-                                    let _ = window.set_focus();
-                                    // Safe positioning - use TopRight as safer alternative
-                                    let _ = window.move_window(tauri_plugin_positioner::Position::TopRight);
-                                }
-                            }
-                        } else if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::Space) || 
+                            windows::toggle_main_window(app);
+                        } else if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::Space) ||
                                   shortcut.matches(tauri_plugin_global_shortcut::Modifiers::SUPER | tauri_plugin_global_shortcut::Modifiers::SHIFT, tauri_plugin_global_shortcut::Code::Space) {
                             // Quick capture toggle
-                            if let Some(window) = app.get_webview_window("quick-capture") {
-                                if window.is_visible().unwrap_or(false) {
-                                    let _ = window.hide();
-                                } else {
-                                    let _ = window.show();
-                                    let _ = window.set_focus();
-                                    let _ = window.move_window(tauri_plugin_positioner::Position::Center);
+                            windows::toggle_quick_capture(app);
+                        } else if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::ALT, tauri_plugin_global_shortcut::Code::KeyZ) ||
+                                  shortcut.matches(tauri_plugin_global_shortcut::Modifiers::SUPER | tauri_plugin_global_shortcut::Modifiers::ALT, tauri_plugin_global_shortcut::Code::KeyZ) {
+                            // Undo the last quick capture
+                            let app = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = step_history(&app, Direction::Undo, true).await {
+                                    eprintln!("Failed to undo quick capture: {}", e);
                                 }
-                            } else {
-                                // Create the quick capture window if it doesn't exist
-                                if let Ok(quick_capture_window) = tauri::WebviewWindowBuilder::new(app, "quick-capture", tauri::WebviewUrl::App("quick-capture.html".into()))
-                                    .inner_size(400.0, 300.0)
-                                    .center()
-                                    .resizable(false)
-                                    .minimizable(false)
-                                    .maximizable(false)
-                                    .decorations(false)
-                                    .always_on_top(true)
-                                    .build() {
-                                    let _ = quick_capture_window.show();
-                                    let _ = quick_capture_window.set_focus();
+                            });
+                        } else if shortcut.matches(tauri_plugin_global_shortcut::Modifiers::CONTROL | tauri_plugin_global_shortcut::Modifiers::ALT, tauri_plugin_global_shortcut::Code::KeyV) ||
+                                  shortcut.matches(tauri_plugin_global_shortcut::Modifiers::SUPER | tauri_plugin_global_shortcut::Modifiers::ALT, tauri_plugin_global_shortcut::Code::KeyV) {
+                            // Capture the clipboard
+                            let app = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = capture_clipboard(&app).await {
+                                    eprintln!("Failed to capture clipboard: {}", e);
                                 }
-                            }
+                            });
                         }
                     }
                 })
@@ -183,19 +854,112 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         )
         .plugin(
             tauri_plugin_sql::Builder::default()
-                .add_migrations("sqlite:daily-notes.db", migrations)
+                .add_migrations(db::DB_URL, db::migrations())
                 .build(),
         )
         .invoke_handler(tauri::generate_handler![
             get_today_note,
             save_today_note,
+            preview_capture,
+            save_capture,
+            get_pending_clip,
+            save_pending_clip,
+            discard_pending_clip,
+            create_note,
+            update_note,
+            delete_note,
+            bulk_update_notes,
+            get_inbox,
+            file_capture,
+            capture_to_task,
+            discard_capture,
+            get_recent_notes,
+            preview_capture_retention,
+            archive_note,
+            unarchive_note,
+            preview_archive_policy,
+            search_notes,
+            undo,
+            redo,
+            undo_last_capture,
+            resolve_date,
+            toggle_task,
+            toggle_pin,
+            toggle_star,
+            reorder_pinned,
+            get_backlinks,
+            get_broken_links,
+            attach_file,
+            attach_bytes,
+            list_attachments,
+            open_attachment,
+            delete_attachment,
+            export_day,
+            set_task_state,
+            list_open_tasks,
+            get_summary,
+            get_sprint_summary,
+            get_notes,
+            get_stats,
+            get_heatmap,
+            get_day,
+            scheduler::list_reminders,
+            scheduler::update_reminder,
+            scheduler::add_note_reminder,
+            scheduler::delete_reminder,
+            settings::get_work_calendar,
+            settings::set_work_calendar,
+            settings::set_day_start_hour,
+            settings::get_sprint,
+            settings::set_sprint,
+            settings::set_inbox_auto_file_hours,
+            settings::set_clipboard_capture_silent,
+            settings::set_fetch_link_titles,
+            settings::set_capture_retention,
+            settings::set_archive_policy,
+            settings::get_settings,
+            settings::get_locale,
+            settings::set_language,
+            autostart::get_launch_at_login,
+            autostart::set_launch_at_login,
+            window_state::get_window_placement,
+            window_state::set_window_placement,
+            windows::open_settings_window,
+            windows::open_quick_capture,
         ])
         .setup(|app| {
-            // Create tray menu synchronously during setup
-            let app_handle = app.handle().clone();
-            tauri::async_runtime::block_on(async move {
-                if let Err(e) = create_tray_menu(&app_handle).await {
-                    eprintln!("Failed to create tray menu: {}", e);
+            let loaded_settings = settings::load(app.handle());
+            days::set_day_start_hour(loaded_settings.day_start_hour);
+            app.manage(settings::SettingsState::new(loaded_settings));
+            app.manage(window_state::load(app.handle()));
+            app.manage(stats::StatsCache::default());
+            app.manage(history::HistoryState::default());
+            app.manage(PendingClip::default());
+            app.manage(DroppedFiles::default());
+
+            // Statistics are recomputed on the next request after notes change
+            let handle = app.handle().clone();
+            app.listen_any(events::NOTES_UPDATED, move |_| {
+                if let Ok(mut cache) = handle.state::<stats::StatsCache>().lock() {
+                    *cache = None;
+                }
+            });
+
+            windows::apply_window_titles(app.handle());
+
+            // Create tray menu, its note entries are filled in asynchronously
+            if let Err(e) = tray::create(app.handle()) {
+                eprintln!("Failed to create tray menu: {}", e);
+            }
+
+            scheduler::start(app.handle());
+            rollover::start(app.handle());
+
+            // Blobs of notes deleted since the last run
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = rollover::collect_attachment_garbage(&handle).await {
+                    eprintln!("Failed to clean up attachments: {}", e);
                 }
             });
 
@@ -203,6 +967,6 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         })
         .on_window_event(handle_window_event)
         .run(tauri::generate_context!())?;
-    
+
     Ok(())
 }
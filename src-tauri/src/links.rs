@@ -0,0 +1,204 @@
+// Links between notes, written as `[[note:123]]` for a note or
+// `[[2026-10-17]]` for a day. Each note's links are parsed into `note_links`
+// whenever its content is saved, which is what backlinks are read from.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::fmt;
+
+use crate::notes::{self, NoteEntry, NOTE_COLUMNS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    Note(i64),
+    Day(NaiveDate),
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Link::Note(id) => write!(f, "[[note:{}]]", id),
+            Link::Day(day) => write!(f, "[[{}]]", day.format("%Y-%m-%d")),
+        }
+    }
+}
+
+/// Links in `content` in order of appearance, each once. Anything else in
+/// double brackets stays text.
+pub fn parse(content: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let inner = &rest[start + 2..];
+        let Some(end) = inner.find("]]") else {
+            break;
+        };
+        let target = &inner[..end];
+        let link = match target.strip_prefix("note:") {
+            Some(id) if id.bytes().all(|b| b.is_ascii_digit()) => id.parse().ok().map(Link::Note),
+            Some(_) => None,
+            None => NaiveDate::parse_from_str(target, "%Y-%m-%d")
+                .ok()
+                .filter(|_| target.len() == 10)
+                .map(Link::Day),
+        };
+        if let Some(link) = link {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+        rest = &inner[end + 2..];
+    }
+    links
+}
+
+/// `content` with every link to note `from` pointing at `to` instead.
+pub fn rewrite(content: &str, from: i64, to: &Link) -> String {
+    content.replace(&Link::Note(from).to_string(), &to.to_string())
+}
+
+/// Replaces the stored links of note `id` with the ones in `content`.
+pub async fn sync(conn: &mut SqliteConnection, id: i64, content: &str) -> Result<(), String> {
+    sqlx::query("DELETE FROM note_links WHERE source_id = ?1")
+        .bind(id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update links: {}", e))?;
+    for link in parse(content) {
+        let (note, day) = match link {
+            Link::Note(target) => (Some(target), None),
+            Link::Day(day) => (None, Some(day.format("%Y-%m-%d").to_string())),
+        };
+        sqlx::query(
+            "INSERT INTO note_links (source_id, target_id, target_day) VALUES (?1, ?2, ?3)",
+        )
+        .bind(id)
+        .bind(note)
+        .bind(day)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update links: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Points every link to note `from` at `to`, e.g. before `from` is deleted
+/// or merged into another note. Returns the rewritten notes before and
+/// after.
+pub async fn retarget(
+    conn: &mut SqliteConnection,
+    from: i64,
+    to: &Link,
+    now: &str,
+) -> Result<Vec<(NoteEntry, NoteEntry)>, String> {
+    let sources = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE id IN (SELECT source_id FROM note_links WHERE target_id = ?1)",
+        NOTE_COLUMNS
+    ))
+    .bind(from)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to fetch linking notes: {}", e))?;
+
+    let mut rewritten = Vec::new();
+    for before in sources {
+        let mut after = before.clone();
+        after.content = rewrite(&before.content, from, to);
+        after.updated_at = now.to_string();
+        notes::restore(&mut *conn, after.id, Some(&after)).await?;
+        rewritten.push((before, after));
+    }
+    Ok(rewritten)
+}
+
+/// Notes linking to `target`, oldest first.
+pub async fn backlinks(pool: &SqlitePool, target: &Link) -> Result<Vec<NoteEntry>, String> {
+    let (note, day) = match target {
+        Link::Note(id) => (Some(*id), None),
+        Link::Day(day) => (None, Some(day.format("%Y-%m-%d").to_string())),
+    };
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE id IN (SELECT source_id FROM note_links WHERE target_id = ?1 OR target_day = ?2)
+         ORDER BY created_at ASC, id ASC",
+        NOTE_COLUMNS
+    ))
+    .bind(note)
+    .bind(day)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch backlinks: {}", e))
+}
+
+/// A link to a note that does not exist.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenLink {
+    pub source: NoteEntry,
+    pub target_id: i64,
+}
+
+pub async fn broken(pool: &SqlitePool) -> Result<Vec<BrokenLink>, String> {
+    let dangling: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT source_id, target_id FROM note_links
+         WHERE target_id IS NOT NULL AND target_id NOT IN (SELECT id FROM notes)
+         ORDER BY source_id, target_id",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch broken links: {}", e))?;
+
+    let mut broken = Vec::new();
+    for (source_id, target_id) in dangling {
+        if let Some(source) = notes::get_note(pool, source_id).await? {
+            broken.push(BrokenLink { source, target_id });
+        }
+    }
+    Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn parses_note_and_day_links_once() {
+        let content =
+            "Follow-up to [[note:12]] from [[2026-10-17]], see [[note:12]] and [[note:7]]";
+        assert_eq!(
+            parse(content),
+            vec![Link::Note(12), Link::Day(day(17)), Link::Note(7)]
+        );
+    }
+
+    #[test]
+    fn other_brackets_stay_text() {
+        assert!(parse("[[note:]] [[note:1a]] [[2026-13-01]] [[2026-10-1]] [[wiki]]").is_empty());
+        assert!(parse("[[note:3").is_empty());
+    }
+
+    #[test]
+    fn links_round_trip_through_display() {
+        for link in [Link::Note(42), Link::Day(day(5))] {
+            assert_eq!(parse(&link.to_string()), vec![link]);
+        }
+    }
+
+    #[test]
+    fn rewriting_only_touches_the_exact_note() {
+        let content = "See [[note:12]] and [[note:123]]";
+        assert_eq!(
+            rewrite(content, 12, &Link::Day(day(17))),
+            "See [[2026-10-17]] and [[note:123]]"
+        );
+        assert_eq!(
+            rewrite(content, 12, &Link::Note(5)),
+            "See [[note:5]] and [[note:123]]"
+        );
+    }
+}
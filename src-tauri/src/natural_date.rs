@@ -0,0 +1,464 @@
+// Natural language dates for backdating notes and scheduling reminders, in
+// German and English: "gestern 16 Uhr", "last friday", "vor 2 Stunden",
+// "yesterday afternoon", "17.10. 9:30", "2026-10-17", "morgen 9 Uhr",
+// "in 30 Minuten". Parsing is strict, any word that is not
+// understood makes the whole input fail so normal text is never mistaken
+// for a date.
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+
+use crate::days;
+
+/// A resolved point in local time. `time` is `None` when only a day was
+/// given, callers then keep the current time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct When {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+impl When {
+    pub fn to_utc(self) -> DateTime<Utc> {
+        match self.time {
+            Some(time) => {
+                let naive = self.date.and_time(time);
+                match Local.from_local_datetime(&naive).earliest() {
+                    Some(local) => local.with_timezone(&Utc),
+                    // Time skipped by a DST jump
+                    None => naive.and_utc(),
+                }
+            }
+            None => days::now_on(self.date),
+        }
+    }
+}
+
+const FILLER: [&str; 6] = ["um", "at", "am", "on", "the", "den"];
+const LAST: [&str; 8] = [
+    "last",
+    "letzten",
+    "letzte",
+    "letzter",
+    "letztes",
+    "letztem",
+    "vergangenen",
+    "vergangene",
+];
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "mon" | "monday" | "mo" | "montag" => Weekday::Mon,
+        "tue" | "tuesday" | "di" | "dienstag" => Weekday::Tue,
+        "wed" | "wednesday" | "mi" | "mittwoch" => Weekday::Wed,
+        "thu" | "thursday" | "do" | "donnerstag" => Weekday::Thu,
+        "fri" | "friday" | "fr" | "freitag" => Weekday::Fri,
+        "sat" | "saturday" | "sa" | "samstag" => Weekday::Sat,
+        "sun" | "sunday" | "so" | "sonntag" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Most recent `weekday` on or before `today`.
+pub fn last_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let back = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    today - Duration::days(back as i64)
+}
+
+fn part_of_day(word: &str) -> Option<NaiveTime> {
+    let hour = match word {
+        "morning" | "morgens" | "vormittags" => 9,
+        "noon" | "mittag" | "mittags" => 12,
+        "afternoon" | "nachmittag" | "nachmittags" => 15,
+        "evening" | "abend" | "abends" => 19,
+        _ => return None,
+    };
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
+fn count(word: &str) -> Option<i64> {
+    match word {
+        "a" | "an" | "one" | "ein" | "eine" | "einer" | "einem" => Some(1),
+        "two" | "zwei" => Some(2),
+        "three" | "drei" => Some(3),
+        _ => word.parse().ok().filter(|n| *n > 0),
+    }
+}
+
+fn times(unit: Duration, amount: i64) -> Option<Duration> {
+    unit.checked_mul(i32::try_from(amount).ok()?)
+}
+
+fn unit(word: &str) -> Option<Duration> {
+    let unit = match word {
+        "m" | "min" | "mins" | "minute" | "minutes" | "minuten" => Duration::minutes(1),
+        "h" | "std" | "hour" | "hours" | "stunde" | "stunden" => Duration::hours(1),
+        "d" | "day" | "days" | "tag" | "tage" | "tagen" => Duration::days(1),
+        "w" | "week" | "weeks" | "woche" | "wochen" => Duration::weeks(1),
+        _ => return None,
+    };
+    Some(unit)
+}
+
+/// Splits `2h` into `("2", "h")`.
+fn split_number(word: &str) -> (&str, &str) {
+    let digits = word
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(word.len());
+    word.split_at(digits)
+}
+
+/// `16:30`, `16:30h`, `16uhr`, `4pm`, `4:30pm`. A bare `16` is only a time
+/// when followed by `uhr`, `am` or `pm`, which `suffix` carries.
+fn clock(word: &str, suffix: Option<&str>) -> Option<NaiveTime> {
+    let (word, meridiem) = match suffix {
+        Some(suffix) => (word, suffix),
+        None => ["uhr", "am", "pm"]
+            .iter()
+            .find_map(|end| word.strip_suffix(end).map(|w| (w, *end)))
+            // `16:30h`, but not `2h` which is a duration
+            .or_else(|| {
+                word.strip_suffix('h')
+                    .filter(|w| w.contains(':'))
+                    .map(|w| (w, "uhr"))
+            })
+            .unwrap_or((word, "")),
+    };
+    let (hour, minute) = match word.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None if !meridiem.is_empty() => (word.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        "am" | "pm" if !(1..=12).contains(&hour) => return None,
+        "am" => hour % 12,
+        "pm" => hour % 12 + 12,
+        _ => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// `2026-10-17`, `17.10.2026`, `17.10.` and `17.10` (current year, or last
+/// year if that day is still ahead).
+fn calendar_date(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let mut parts = word.trim_end_matches('.').split('.');
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    match parts.next() {
+        Some(_) if parts.next().is_some() => None,
+        Some(year) => NaiveDate::from_ymd_opt(year.parse().ok()?, month, day),
+        None => {
+            let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+            if date > today {
+                NaiveDate::from_ymd_opt(today.year() - 1, month, day)
+            } else {
+                Some(date)
+            }
+        }
+    }
+}
+
+/// Parses `input` relative to the local time `now`.
+pub fn parse(input: &str, now: NaiveDateTime) -> Option<When> {
+    let input = input.to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let today = now.date();
+    let mut date = None;
+    let mut time = None;
+    let mut understood = false;
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let next = words.get(i + 1).copied();
+        i += 1;
+
+        if FILLER.contains(&word) {
+            continue;
+        }
+        understood = true;
+
+        match word {
+            "now" | "jetzt" => {
+                date = Some(today);
+                time = Some(now.time());
+                continue;
+            }
+            "today" | "heute" => {
+                date = Some(today);
+                continue;
+            }
+            "yesterday" | "gestern" => {
+                date = today.pred_opt();
+                continue;
+            }
+            "vorgestern" => {
+                date = today.pred_opt()?.pred_opt();
+                continue;
+            }
+            "tomorrow" | "morgen" => {
+                date = today.succ_opt();
+                continue;
+            }
+            "übermorgen" => {
+                date = today.succ_opt()?.succ_opt();
+                continue;
+            }
+            "day" if next == Some("before") && words.get(i + 1) == Some(&"yesterday") => {
+                date = today.pred_opt()?.pred_opt();
+                i += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(weekday) = weekday(word) {
+            date = Some(last_weekday(today, weekday));
+            continue;
+        }
+        if LAST.contains(&word) {
+            // "last friday" on a Friday means a week ago
+            let weekday = weekday(next?)?;
+            date = Some(last_weekday(today.pred_opt()?, weekday));
+            i += 1;
+            continue;
+        }
+        if let Some(part) = part_of_day(word) {
+            time = time.or(Some(part));
+            continue;
+        }
+
+        // "vor 2 Stunden", "vor einer Stunde", "vor 2h", "in 30 Minuten"
+        let relative = if word == "vor" || word == "in" {
+            let amount = next?;
+            i += 1;
+            let (number, rest) = split_number(amount);
+            let offset = if rest.is_empty() || number.is_empty() {
+                let unit = unit(words.get(i)?)?;
+                i += 1;
+                times(unit, count(amount)?)?
+            } else {
+                times(unit(rest)?, count(number)?)?
+            };
+            Some(if word == "in" { -offset } else { offset })
+        // "2 hours ago", "an hour ago", "2h ago"
+        } else if let Some(amount) = count(word) {
+            match (next.and_then(unit), words.get(i + 1)) {
+                (Some(unit), Some(&"ago")) => {
+                    i += 2;
+                    Some(times(unit, amount)?)
+                }
+                _ => None,
+            }
+        } else {
+            match split_number(word) {
+                (number, rest) if !number.is_empty() && next == Some("ago") => {
+                    i += 1;
+                    Some(times(unit(rest)?, count(number)?)?)
+                }
+                _ => None,
+            }
+        };
+        if let Some(offset) = relative {
+            let at = now.checked_sub_signed(offset)?;
+            date = Some(at.date());
+            if offset.abs() < Duration::days(1) {
+                time = Some(at.time());
+            }
+            continue;
+        }
+
+        let suffix = next.filter(|n| ["uhr", "am", "pm"].contains(n));
+        if let Some(clock) = clock(word, suffix) {
+            time = Some(clock);
+            if suffix.is_some() {
+                i += 1;
+            }
+            continue;
+        }
+        if let Some(day) = calendar_date(word, today) {
+            date = Some(day);
+            continue;
+        }
+        return None;
+    }
+
+    if !understood || (date.is_none() && time.is_none()) {
+        return None;
+    }
+    Some(When {
+        date: date.unwrap_or(today),
+        time,
+    })
+}
+
+/// Accepts an RFC 3339 timestamp as sent by the frontend, or natural
+/// language resolved against the current local time.
+pub fn resolve(input: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input.trim()) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    parse(input, Local::now().naive_local())
+        .map(When::to_utc)
+        .ok_or_else(|| format!("Could not understand date: {}", input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Monday, 19.10.2026 10:30
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+            .unwrap()
+    }
+
+    fn at(month: u32, day: u32, time: Option<(u32, u32)>) -> Option<When> {
+        Some(When {
+            date: NaiveDate::from_ymd_opt(2026, month, day).unwrap(),
+            time: time.map(|(h, m)| NaiveTime::from_hms_opt(h, m, 0).unwrap()),
+        })
+    }
+
+    #[test]
+    fn relative_days() {
+        assert_eq!(parse("heute", now()), at(10, 19, None));
+        assert_eq!(parse("Yesterday", now()), at(10, 18, None));
+        assert_eq!(parse("gestern", now()), at(10, 18, None));
+        assert_eq!(parse("vorgestern", now()), at(10, 17, None));
+        assert_eq!(parse("day before yesterday", now()), at(10, 17, None));
+    }
+
+    #[test]
+    fn days_with_clock_time() {
+        assert_eq!(parse("gestern 16 Uhr", now()), at(10, 18, Some((16, 0))));
+        assert_eq!(parse("gestern um 16:30", now()), at(10, 18, Some((16, 30))));
+        assert_eq!(parse("gestern 16uhr", now()), at(10, 18, Some((16, 0))));
+        assert_eq!(parse("yesterday at 4pm", now()), at(10, 18, Some((16, 0))));
+        assert_eq!(
+            parse("yesterday 4:30 pm", now()),
+            at(10, 18, Some((16, 30)))
+        );
+        assert_eq!(parse("yesterday 12am", now()), at(10, 18, Some((0, 0))));
+    }
+
+    #[test]
+    fn clock_time_alone_means_today() {
+        assert_eq!(parse("um 9 Uhr", now()), at(10, 19, Some((9, 0))));
+        assert_eq!(parse("9:15", now()), at(10, 19, Some((9, 15))));
+        assert_eq!(parse("16:45h", now()), at(10, 19, Some((16, 45))));
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(parse("friday", now()), at(10, 16, None));
+        assert_eq!(parse("am Freitag", now()), at(10, 16, None));
+        assert_eq!(parse("last friday", now()), at(10, 16, None));
+        assert_eq!(
+            parse("letzten Dienstag 14 Uhr", now()),
+            at(10, 13, Some((14, 0)))
+        );
+        // Today is a Monday
+        assert_eq!(parse("montag", now()), at(10, 19, None));
+        assert_eq!(parse("last monday", now()), at(10, 12, None));
+    }
+
+    #[test]
+    fn parts_of_day() {
+        assert_eq!(
+            parse("yesterday afternoon", now()),
+            at(10, 18, Some((15, 0)))
+        );
+        assert_eq!(parse("gestern abends", now()), at(10, 18, Some((19, 0))));
+        assert_eq!(
+            parse("freitag nachmittags", now()),
+            at(10, 16, Some((15, 0)))
+        );
+    }
+
+    #[test]
+    fn durations_ago() {
+        assert_eq!(parse("vor 2 Stunden", now()), at(10, 19, Some((8, 30))));
+        assert_eq!(parse("vor einer Stunde", now()), at(10, 19, Some((9, 30))));
+        assert_eq!(parse("vor 45 Minuten", now()), at(10, 19, Some((9, 45))));
+        assert_eq!(parse("vor 2h", now()), at(10, 19, Some((8, 30))));
+        assert_eq!(parse("2 hours ago", now()), at(10, 19, Some((8, 30))));
+        assert_eq!(parse("an hour ago", now()), at(10, 19, Some((9, 30))));
+        assert_eq!(parse("12h ago", now()), at(10, 18, Some((22, 30))));
+    }
+
+    #[test]
+    fn days_ago_keep_time_open() {
+        assert_eq!(parse("vor 3 Tagen", now()), at(10, 16, None));
+        assert_eq!(parse("2 days ago", now()), at(10, 17, None));
+        assert_eq!(
+            parse("vor 3 Tagen um 11 Uhr", now()),
+            at(10, 16, Some((11, 0)))
+        );
+    }
+
+    #[test]
+    fn future_dates_for_reminders() {
+        assert_eq!(parse("morgen 9 Uhr", now()), at(10, 20, Some((9, 0))));
+        assert_eq!(parse("tomorrow morning", now()), at(10, 20, Some((9, 0))));
+        assert_eq!(parse("übermorgen", now()), at(10, 21, None));
+        assert_eq!(parse("in 30 Minuten", now()), at(10, 19, Some((11, 0))));
+        assert_eq!(parse("in 2h", now()), at(10, 19, Some((12, 30))));
+        assert_eq!(parse("in 3 days", now()), at(10, 22, None));
+    }
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(parse("2026-10-02", now()), at(10, 2, None));
+        assert_eq!(parse("17.10.", now()), at(10, 17, None));
+        assert_eq!(parse("17.10.2026 9:30", now()), at(10, 17, Some((9, 30))));
+        // Still ahead this year, so it was last year
+        assert_eq!(
+            parse("24.12.", now()).map(|w| w.date),
+            NaiveDate::from_ymd_opt(2025, 12, 24)
+        );
+    }
+
+    #[test]
+    fn nonsense_is_rejected() {
+        for input in [
+            "",
+            "am",
+            "alice",
+            "gestern bei alice",
+            "16",
+            "25:00",
+            "13pm",
+            "vor",
+            "vor 2",
+            "vor 2 äpfeln",
+            "vor 99999999999 wochen",
+            "2h",
+            "2 hours",
+            "last",
+            "last week",
+            "31.02.",
+            "1.2.2026.5",
+            "1.2.3.4.",
+        ] {
+            assert_eq!(parse(input, now()), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn resolve_accepts_rfc3339() {
+        assert_eq!(
+            resolve("2026-10-18T14:00:00.000Z").unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 18, 14, 0, 0).unwrap()
+        );
+        assert!(resolve("blah").is_err());
+    }
+}
@@ -0,0 +1,890 @@
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
+
+use crate::days;
+use crate::history::{Change, Dependents};
+use crate::links::{self, Link};
+use crate::tasks::{self, TaskState};
+
+/// Standup section a note belongs to. Notes without one count as done work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    Done,
+    Todo,
+    Blocker,
+}
+
+impl Section {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Section::Done => "done",
+            Section::Todo => "todo",
+            Section::Blocker => "blocker",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Section> {
+        match value {
+            "done" => Some(Section::Done),
+            "todo" => Some(Section::Todo),
+            "blocker" => Some(Section::Blocker),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Priority> {
+        match value {
+            "low" => Some(Priority::Low),
+            "medium" => Some(Priority::Medium),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteEntry {
+    pub id: i64,
+    pub content: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub is_quick_capture: bool,
+    pub section: Option<Section>,
+    pub priority: Option<Priority>,
+    pub duration_minutes: Option<u32>,
+    pub tags: Vec<String>,
+    /// `None` for plain notes
+    pub task_state: Option<TaskState>,
+    pub completed_at: Option<String>,
+    /// When a quick capture left the inbox, see `inbox`
+    pub processed_at: Option<String>,
+    /// Position among the pinned notes, `None` when not pinned
+    pub pin_order: Option<i64>,
+    pub starred: bool,
+    /// When the note was archived, archived notes are hidden from default
+    /// listings
+    pub archived_at: Option<String>,
+}
+
+/// Columns every note query selects, tags folded into one comma separated
+/// string so a note is still a single row.
+pub const NOTE_COLUMNS: &str = "id, content, created_at, updated_at, is_quick_capture, \
+     section, priority, duration_minutes, task_state, completed_at, processed_at, \
+     pin_order, starred, archived_at, \
+     (SELECT GROUP_CONCAT(tag, ',') FROM note_tags WHERE note_id = notes.id) AS tags";
+
+impl<'r> FromRow<'r, SqliteRow> for NoteEntry {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let section: Option<String> = row.try_get("section")?;
+        let priority: Option<String> = row.try_get("priority")?;
+        let duration: Option<i64> = row.try_get("duration_minutes")?;
+        let tags: Option<String> = row.try_get("tags")?;
+        let task_state: Option<String> = row.try_get("task_state")?;
+        let mut tags: Vec<String> = tags
+            .unwrap_or_default()
+            .split(',')
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+        tags.sort();
+        Ok(NoteEntry {
+            id: row.try_get("id")?,
+            content: row.try_get("content")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            is_quick_capture: row.try_get("is_quick_capture")?,
+            section: section.as_deref().and_then(Section::parse),
+            priority: priority.as_deref().and_then(Priority::parse),
+            duration_minutes: duration.and_then(|d| u32::try_from(d).ok()),
+            tags,
+            task_state: task_state.as_deref().and_then(TaskState::parse),
+            completed_at: row.try_get("completed_at")?,
+            processed_at: row.try_get("processed_at")?,
+            pin_order: row.try_get("pin_order")?,
+            starred: row.try_get("starred")?,
+            archived_at: row.try_get("archived_at")?,
+        })
+    }
+}
+
+/// A note about to be inserted.
+#[derive(Debug, Clone)]
+pub struct NewNote {
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub is_quick_capture: bool,
+    pub section: Option<Section>,
+    pub priority: Option<Priority>,
+    pub duration_minutes: Option<u32>,
+    pub tags: Vec<String>,
+    pub task_state: Option<TaskState>,
+}
+
+#[cfg(test)]
+impl NewNote {
+    /// A plain note without metadata.
+    pub fn text(content: &str, created_at: DateTime<Utc>) -> NewNote {
+        NewNote {
+            content: content.to_string(),
+            created_at,
+            is_quick_capture: false,
+            section: None,
+            priority: None,
+            duration_minutes: None,
+            tags: Vec::new(),
+            task_state: None,
+        }
+    }
+}
+
+/// Formats a timestamp the way the frontend writes them (`Date.toISOString()`),
+/// so string comparisons in SQL stay consistent.
+pub fn timestamp(dt: &DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Notes of one local day, oldest first.
+pub async fn notes_for_day(pool: &SqlitePool, day: NaiveDate) -> Result<Vec<NoteEntry>, String> {
+    notes_in_range(pool, day, day).await
+}
+
+/// Notes from the start of `from` to the end of `to`, oldest first.
+/// Archived notes are included, so summaries and exports cover them.
+pub async fn notes_in_range(
+    pool: &SqlitePool,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<NoteEntry>, String> {
+    let (start, end) = days::days_range(from, to);
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE created_at >= ?1 AND created_at < ?2 AND TRIM(content) != ''
+         ORDER BY created_at ASC",
+        NOTE_COLUMNS
+    ))
+    .bind(timestamp(&start))
+    .bind(timestamp(&end))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch notes: {}", e))
+}
+
+/// Number of notes on one local day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayTotals {
+    pub day: NaiveDate,
+    pub notes: usize,
+    pub quick_captures: usize,
+}
+
+/// Note counts grouped by local day in SQL, only days with notes, oldest
+/// first. Without a range the whole history is counted.
+pub async fn day_totals(
+    pool: &SqlitePool,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<DayTotals>, String> {
+    let (start, end) = match range {
+        Some((from, to)) => {
+            let (start, end) = days::days_range(from, to);
+            (Some(timestamp(&start)), Some(timestamp(&end)))
+        }
+        None => (None, None),
+    };
+    let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT date(created_at, 'localtime', ?1) AS day, COUNT(*), SUM(is_quick_capture)
+         FROM notes
+         WHERE TRIM(content) != ''
+           AND (?2 IS NULL OR created_at >= ?2) AND (?3 IS NULL OR created_at < ?3)
+         GROUP BY day ORDER BY day",
+    )
+    .bind(days::sqlite_day_shift())
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to count notes: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(day, notes, quick)| {
+            Some(DayTotals {
+                day: NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?,
+                notes: usize::try_from(notes).ok()?,
+                quick_captures: usize::try_from(quick).ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Sorts pinned notes ahead of the rest, in the order the user gave them.
+const PINNED_FIRST: &str = "pin_order IS NULL, pin_order";
+
+/// Pinned notes in their user-defined order, without archived ones.
+pub async fn pinned(pool: &SqlitePool) -> Result<Vec<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE pin_order IS NOT NULL AND archived_at IS NULL
+         ORDER BY pin_order, id",
+        NOTE_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch pinned notes: {}", e))
+}
+
+/// What a day shows. Which day is today and what carries over into it is
+/// decided here, with the configured day start, not in the frontend. The
+/// main window, the tray and exports all build on this.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DayView {
+    /// Pinned notes from any day in their order, only today
+    pub pinned: Vec<NoteEntry>,
+    /// Open tasks from earlier days, oldest first, only today
+    pub carried_over: Vec<NoteEntry>,
+    /// The day's notes oldest first, without the pinned ones listed above
+    pub notes: Vec<NoteEntry>,
+    /// Archived notes of the day, not shown but still exported
+    pub archived: Vec<NoteEntry>,
+}
+
+impl DayView {
+    /// Everything the day's standup covers, in the order shown, archived
+    /// notes among the day's notes.
+    pub fn into_entries(self) -> Vec<NoteEntry> {
+        let mut own = self.notes;
+        own.extend(self.archived);
+        own.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        let mut entries = self.pinned;
+        entries.extend(self.carried_over);
+        entries.extend(own);
+        entries
+    }
+}
+
+pub async fn day_view(pool: &SqlitePool, day: NaiveDate) -> Result<DayView, String> {
+    let mut view = DayView::default();
+    if day == days::today() {
+        view.pinned = pinned(pool).await?;
+        view.carried_over = tasks::carried_over(pool, day).await?;
+        // Pinned tasks are listed with the pinned notes already
+        view.carried_over.retain(|task| task.pin_order.is_none());
+    }
+    for note in notes_for_day(pool, day).await? {
+        if view.pinned.iter().any(|pinned| pinned.id == note.id) {
+            continue;
+        }
+        if note.archived_at.is_some() {
+            view.archived.push(note);
+        } else {
+            view.notes.push(note);
+        }
+    }
+    Ok(view)
+}
+
+pub async fn get_note(pool: &SqlitePool, id: i64) -> Result<Option<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!("SELECT {} FROM notes WHERE id = ?1", NOTE_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch note: {}", e))
+}
+
+/// Inserts a note together with its tags.
+pub async fn insert_note(pool: &SqlitePool, note: &NewNote) -> Result<NoteEntry, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let now = timestamp(&Utc::now());
+    let id = sqlx::query(
+        "INSERT INTO notes
+         (content, created_at, updated_at, is_quick_capture, section, priority, duration_minutes,
+          task_state, completed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )
+    .bind(&note.content)
+    .bind(timestamp(&note.created_at))
+    .bind(&now)
+    .bind(note.is_quick_capture)
+    .bind(note.section.map(Section::as_str))
+    .bind(note.priority.map(Priority::as_str))
+    .bind(note.duration_minutes)
+    .bind(note.task_state.map(TaskState::as_str))
+    .bind(
+        note.task_state
+            .filter(|s| s.is_closed())
+            .map(|_| now.clone()),
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to insert note: {}", e))?
+    .last_insert_rowid();
+
+    for tag in &note.tags {
+        sqlx::query("INSERT OR IGNORE INTO note_tags (note_id, tag) VALUES (?1, ?2)")
+            .bind(id)
+            .bind(tag)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to insert tag: {}", e))?;
+    }
+    links::sync(&mut tx, id, &note.content).await?;
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit note: {}", e))?;
+
+    get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Inserted note not found".to_string())
+}
+
+/// Puts note `id` back into an earlier state, all fields and tags included.
+/// `None` removes the note.
+pub async fn restore(
+    conn: &mut SqliteConnection,
+    id: i64,
+    state: Option<&NoteEntry>,
+) -> Result<(), String> {
+    let Some(note) = state else {
+        sqlx::query("DELETE FROM notes WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to delete note: {}", e))?;
+        return Ok(());
+    };
+    // An upsert, `INSERT OR REPLACE` would cascade into tags and reminders
+    sqlx::query(
+        "INSERT INTO notes
+         (id, content, created_at, updated_at, is_quick_capture, section, priority,
+          duration_minutes, task_state, completed_at, processed_at, pin_order, starred,
+          archived_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+         ON CONFLICT(id) DO UPDATE SET
+          content = excluded.content, created_at = excluded.created_at,
+          updated_at = excluded.updated_at, is_quick_capture = excluded.is_quick_capture,
+          section = excluded.section, priority = excluded.priority,
+          duration_minutes = excluded.duration_minutes, task_state = excluded.task_state,
+          completed_at = excluded.completed_at, processed_at = excluded.processed_at,
+          pin_order = excluded.pin_order, starred = excluded.starred,
+          archived_at = excluded.archived_at",
+    )
+    .bind(note.id)
+    .bind(&note.content)
+    .bind(&note.created_at)
+    .bind(&note.updated_at)
+    .bind(note.is_quick_capture)
+    .bind(note.section.map(Section::as_str))
+    .bind(note.priority.map(Priority::as_str))
+    .bind(note.duration_minutes)
+    .bind(note.task_state.map(TaskState::as_str))
+    .bind(&note.completed_at)
+    .bind(&note.processed_at)
+    .bind(note.pin_order)
+    .bind(note.starred)
+    .bind(&note.archived_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to restore note: {}", e))?;
+
+    sqlx::query("DELETE FROM note_tags WHERE note_id = ?1")
+        .bind(note.id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to restore tags: {}", e))?;
+    for tag in &note.tags {
+        sqlx::query("INSERT OR IGNORE INTO note_tags (note_id, tag) VALUES (?1, ?2)")
+            .bind(note.id)
+            .bind(tag)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to restore tags: {}", e))?;
+    }
+    links::sync(conn, note.id, &note.content).await
+}
+
+/// Updates a note's content and optionally moves it to another point in time.
+pub async fn update_note(
+    pool: &SqlitePool,
+    id: i64,
+    content: &str,
+    created_at: Option<DateTime<Utc>>,
+) -> Result<NoteEntry, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let result = sqlx::query(
+        "UPDATE notes SET content = ?1, updated_at = ?2, created_at = COALESCE(?3, created_at)
+         WHERE id = ?4",
+    )
+    .bind(content)
+    .bind(timestamp(&Utc::now()))
+    .bind(created_at.as_ref().map(timestamp))
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update note: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err("Note not found".to_string());
+    }
+    links::sync(&mut tx, id, content).await?;
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit note: {}", e))?;
+    get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())
+}
+
+/// Pinned notes, then the newest notes created at or after `since`.
+pub async fn recent_notes(
+    pool: &SqlitePool,
+    since: &DateTime<Utc>,
+    limit: u32,
+) -> Result<Vec<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE (pin_order IS NOT NULL OR created_at >= ?1) AND archived_at IS NULL
+           AND TRIM(content) != ''
+         ORDER BY {}, created_at DESC, id DESC LIMIT ?2",
+        NOTE_COLUMNS, PINNED_FIRST
+    ))
+    .bind(timestamp(since))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch notes: {}", e))
+}
+
+const SEARCH_LIMIT: u32 = 200;
+
+/// Notes containing `query`, case-insensitive, newest first. `archived`
+/// narrows the result to archived or active notes, `None` finds both. An
+/// empty query only lists the archive.
+pub async fn search(
+    pool: &SqlitePool,
+    query: &str,
+    archived: Option<bool>,
+) -> Result<Vec<NoteEntry>, String> {
+    let query = query.trim();
+    if query.is_empty() && archived != Some(true) {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE INSTR(LOWER(content), LOWER(?1)) > 0 AND TRIM(content) != ''
+           AND (?2 IS NULL OR (archived_at IS NOT NULL) = ?2)
+         ORDER BY created_at DESC, id DESC LIMIT ?3",
+        NOTE_COLUMNS
+    ))
+    .bind(query)
+    .bind(archived)
+    .bind(SEARCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to search notes: {}", e))
+}
+
+/// Deletes a note, its tags, attachments and reminders go with it. Links to
+/// it are rewritten to its day. Returns the changes for the history, the
+/// notes whose links changed first.
+pub async fn delete_note(pool: &SqlitePool, id: i64) -> Result<Vec<Change>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let note = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE id = ?1",
+        NOTE_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch note: {}", e))?
+    .ok_or_else(|| "Note not found".to_string())?;
+    let mut changes: Vec<Change> = unlink(&mut tx, &note, &timestamp(&Utc::now()))
+        .await?
+        .iter()
+        .map(|(before, after)| Change::updated(before, after))
+        .collect();
+    let dependents = Dependents::load(&mut tx, id).await?;
+    sqlx::query("DELETE FROM notes WHERE id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete note: {}", e))?;
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit note: {}", e))?;
+    changes.push(Change::deleted(&note, dependents));
+    Ok(changes)
+}
+
+/// Points links to `note`, which is about to go away, at its day instead.
+pub async fn unlink(
+    conn: &mut SqliteConnection,
+    note: &NoteEntry,
+    now: &str,
+) -> Result<Vec<(NoteEntry, NoteEntry)>, String> {
+    match days::local_day_of(&note.created_at) {
+        Some(day) => links::retarget(conn, note.id, &Link::Day(day), now).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Local days before `before` that have at least one note, newest first.
+pub async fn recent_days(
+    pool: &SqlitePool,
+    before: NaiveDate,
+    lookback_days: u64,
+    limit: usize,
+) -> Result<Vec<NaiveDate>, String> {
+    let earliest = before
+        .checked_sub_days(chrono::Days::new(lookback_days))
+        .unwrap_or(before);
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT created_at FROM notes
+         WHERE created_at >= ?1 AND created_at < ?2 AND TRIM(content) != ''
+         ORDER BY created_at DESC",
+    )
+    .bind(timestamp(&days::start_of_day(earliest)))
+    .bind(timestamp(&days::start_of_day(before)))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch recent days: {}", e))?;
+
+    let mut result: Vec<NaiveDate> = Vec::new();
+    for (created_at,) in rows {
+        if let Some(day) = days::local_day_of(&created_at) {
+            if result.last() != Some(&day) {
+                result.push(day);
+            }
+        }
+        if result.len() >= limit {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+/// Position in the newest-first note list. Pages continue from the exact
+/// note instead of an offset, so inserts do not shift later pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: String,
+    pub id: i64,
+}
+
+impl Cursor {
+    fn of(note: &NoteEntry) -> Cursor {
+        Cursor {
+            created_at: note.created_at.clone(),
+            id: note.id,
+        }
+    }
+
+    /// Opaque token handed to the frontend.
+    pub fn encode(&self) -> String {
+        format!("{}|{}", self.id, self.created_at)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn decode(token: &str) -> Option<Cursor> {
+        if !token.len().is_multiple_of(2) {
+            return None;
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(token.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let (id, created_at) = raw.split_once('|')?;
+        Some(Cursor {
+            created_at: created_at.to_string(),
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageDirection {
+    /// Further down the list, towards older notes
+    #[default]
+    Older,
+    /// Back up the list, towards newer notes
+    Newer,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotePage {
+    /// Pinned notes, only on the first page. The timeline below leaves them
+    /// out so they are not listed twice.
+    pub pinned: Vec<NoteEntry>,
+    /// Newest first, regardless of direction
+    pub notes: Vec<NoteEntry>,
+    /// Continues with older notes, `None` at the end of the list
+    pub older: Option<String>,
+    /// Continues with newer notes. Set even at the top of the list, since
+    /// notes may be added later. `None` only while there are no notes.
+    pub newer: Option<String>,
+}
+
+/// One page of the newest-first note list, starting after `cursor` in
+/// `direction`, or at the newest note without a cursor.
+pub async fn list_page(
+    pool: &SqlitePool,
+    cursor: Option<&Cursor>,
+    direction: PageDirection,
+    limit: Option<u32>,
+) -> Result<NotePage, String> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    // `created_at, id` matches the index on created_at, which carries the rowid
+    let (condition, order) = match (cursor, direction) {
+        (None, _) => ("1", "DESC"),
+        (Some(_), PageDirection::Older) => ("(created_at, id) < (?1, ?2)", "DESC"),
+        (Some(_), PageDirection::Newer) => ("(created_at, id) > (?1, ?2)", "ASC"),
+    };
+    // One extra row tells whether another page follows
+    let sql = format!(
+        "SELECT {} FROM notes
+         WHERE {} AND pin_order IS NULL AND archived_at IS NULL AND TRIM(content) != ''
+         ORDER BY created_at {order}, id {order} LIMIT ?3",
+        NOTE_COLUMNS,
+        condition,
+        order = order
+    );
+    let mut notes = sqlx::query_as::<_, NoteEntry>(&sql)
+        .bind(cursor.map(|c| c.created_at.clone()))
+        .bind(cursor.map(|c| c.id))
+        .bind(i64::from(limit) + 1)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch notes: {}", e))?;
+
+    let has_more = notes.len() > limit as usize;
+    notes.truncate(limit as usize);
+    if direction == PageDirection::Newer && cursor.is_some() {
+        notes.reverse();
+    }
+
+    // Paging up always leaves at least the cursor's note below
+    let more_older = match (cursor, direction) {
+        (Some(_), PageDirection::Newer) => true,
+        _ => has_more,
+    };
+    // An empty page hands back the cursor it started from
+    let older = notes
+        .last()
+        .map(Cursor::of)
+        .or_else(|| cursor.cloned())
+        .filter(|_| more_older);
+    let newer = notes.first().map(Cursor::of).or_else(|| cursor.cloned());
+    let pinned = match cursor {
+        None => pinned(pool).await?,
+        Some(_) => Vec::new(),
+    };
+    Ok(NotePage {
+        pinned,
+        notes,
+        older: older.map(|c| c.encode()),
+        newer: newer.map(|c| c.encode()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = Cursor {
+            created_at: "2026-10-19T08:30:00.000Z".to_string(),
+            id: 42,
+        };
+        let token = cursor.encode();
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(Cursor::decode(&token), Some(cursor));
+    }
+
+    #[test]
+    fn invalid_cursors_are_rejected() {
+        assert_eq!(Cursor::decode("abc"), None);
+        assert_eq!(Cursor::decode("zz"), None);
+        // "42" without a separator
+        assert_eq!(Cursor::decode("3432"), None);
+    }
+
+    fn minute(m: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-19T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + chrono::Duration::minutes(m)
+    }
+
+    /// Notes at minutes `0..count`, returns their ids oldest first.
+    async fn fill(pool: &SqlitePool, count: i64) -> Vec<i64> {
+        let mut ids = Vec::new();
+        for m in 0..count {
+            let note = insert_note(pool, &NewNote::text(&format!("Note {}", m), minute(m)))
+                .await
+                .unwrap();
+            ids.push(note.id);
+        }
+        ids
+    }
+
+    async fn page(pool: &SqlitePool, token: Option<&String>, direction: PageDirection) -> NotePage {
+        let cursor = token.map(|t| Cursor::decode(t).unwrap());
+        list_page(pool, cursor.as_ref(), direction, Some(2))
+            .await
+            .unwrap()
+    }
+
+    fn ids_of(page: &NotePage) -> Vec<i64> {
+        page.notes.iter().map(|n| n.id).collect()
+    }
+
+    #[tokio::test]
+    async fn older_pages_ignore_notes_added_meanwhile() {
+        let pool = db::test_pool().await;
+        let ids = fill(&pool, 5).await;
+        let first = page(&pool, None, PageDirection::Older).await;
+        assert_eq!(ids_of(&first), vec![ids[4], ids[3]]);
+
+        // Newer than everything, and a tie with the cursor's note
+        insert_note(&pool, &NewNote::text("Later", minute(10)))
+            .await
+            .unwrap();
+        insert_note(&pool, &NewNote::text("Same minute", minute(3)))
+            .await
+            .unwrap();
+
+        let second = page(&pool, first.older.as_ref(), PageDirection::Older).await;
+        assert_eq!(ids_of(&second), vec![ids[2], ids[1]]);
+        let third = page(&pool, second.older.as_ref(), PageDirection::Older).await;
+        assert_eq!(ids_of(&third), vec![ids[0]]);
+    }
+
+    #[tokio::test]
+    async fn newer_pages_walk_back_up_newest_first() {
+        let pool = db::test_pool().await;
+        let ids = fill(&pool, 5).await;
+        let first = page(&pool, None, PageDirection::Older).await;
+        let second = page(&pool, first.older.as_ref(), PageDirection::Older).await;
+
+        let back = page(&pool, second.newer.as_ref(), PageDirection::Newer).await;
+        assert_eq!(ids_of(&back), vec![ids[4], ids[3]]);
+        assert!(back.older.is_some());
+        assert!(back.pinned.is_empty());
+
+        let later = insert_note(&pool, &NewNote::text("Later", minute(10)))
+            .await
+            .unwrap();
+        let top = page(&pool, back.newer.as_ref(), PageDirection::Newer).await;
+        assert_eq!(ids_of(&top), vec![later.id]);
+    }
+
+    #[tokio::test]
+    async fn cursors_at_the_ends_of_the_list() {
+        let pool = db::test_pool().await;
+        let empty = page(&pool, None, PageDirection::Older).await;
+        assert!(empty.notes.is_empty());
+        assert_eq!((empty.older, empty.newer), (None, None));
+
+        let ids = fill(&pool, 4).await;
+        let first = page(&pool, None, PageDirection::Older).await;
+        let last = page(&pool, first.older.as_ref(), PageDirection::Older).await;
+        assert_eq!(ids_of(&last), vec![ids[1], ids[0]]);
+        assert_eq!(last.older, None);
+
+        // Nothing newer yet, the top hands back its own cursor
+        let above = page(&pool, first.newer.as_ref(), PageDirection::Newer).await;
+        assert!(above.notes.is_empty());
+        assert_eq!(above.newer, first.newer);
+        assert!(above.older.is_some());
+    }
+
+    #[tokio::test]
+    async fn today_lists_pinned_notes_carried_over_tasks_and_its_own_notes() {
+        let pool = db::test_pool().await;
+        let today = days::today();
+        let yesterday = today - chrono::Duration::days(1);
+        let earlier = today - chrono::Duration::days(3);
+        let at = |day| days::start_of_day(day) + chrono::Duration::hours(1);
+        let insert = |content, day, task_state| {
+            let mut note = NewNote::text(content, at(day));
+            note.task_state = task_state;
+            let pool = pool.clone();
+            async move { insert_note(&pool, &note).await.unwrap() }
+        };
+        let set = |column: &'static str, id: i64| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query(&format!("UPDATE notes SET {} WHERE id = ?1", column))
+                    .bind(id)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+        };
+        let on_call = insert("On call", earlier, None).await;
+        set("pin_order = 1", on_call.id).await;
+        let release = insert("Release", yesterday, Some(TaskState::Open)).await;
+        set("pin_order = 0", release.id).await;
+        let review = insert("Review PR", yesterday, Some(TaskState::Open)).await;
+        insert("Deploy", yesterday, Some(TaskState::Done)).await;
+        let standup = insert("Standup", today, None).await;
+        let old = insert("Old idea", today, None).await;
+        set("archived_at = created_at", old.id).await;
+
+        let ids = |notes: &[NoteEntry]| notes.iter().map(|n| n.id).collect::<Vec<_>>();
+        let view = day_view(&pool, today).await.unwrap();
+        assert_eq!(ids(&view.pinned), vec![release.id, on_call.id]);
+        assert_eq!(ids(&view.carried_over), vec![review.id]);
+        assert_eq!(ids(&view.notes), vec![standup.id]);
+        assert_eq!(ids(&view.archived), vec![old.id]);
+        assert_eq!(
+            ids(&view.into_entries()),
+            vec![release.id, on_call.id, review.id, standup.id, old.id]
+        );
+
+        // Other days only have their own notes, pinned ones included
+        let view = day_view(&pool, earlier).await.unwrap();
+        assert!(view.pinned.is_empty() && view.carried_over.is_empty());
+        assert_eq!(ids(&view.notes), vec![on_call.id]);
+        let before = earlier - chrono::Duration::days(1);
+        assert!(day_view(&pool, before)
+            .await
+            .unwrap()
+            .into_entries()
+            .is_empty());
+    }
+}
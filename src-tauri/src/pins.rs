@@ -0,0 +1,95 @@
+use sqlx::SqlitePool;
+
+use crate::notes::{self, NoteEntry};
+
+/// Pins a note at the end of the pinned list, or unpins it.
+pub async fn toggle_pin(pool: &SqlitePool, id: i64) -> Result<NoteEntry, String> {
+    let result = sqlx::query(
+        "UPDATE notes SET pin_order = CASE
+             WHEN pin_order IS NULL THEN (SELECT COALESCE(MAX(pin_order), -1) + 1 FROM notes)
+             ELSE NULL
+         END
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to pin note: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err("Note not found".to_string());
+    }
+    notes::get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())
+}
+
+pub async fn toggle_star(pool: &SqlitePool, id: i64) -> Result<NoteEntry, String> {
+    let result = sqlx::query("UPDATE notes SET starred = NOT starred WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to star note: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err("Note not found".to_string());
+    }
+    notes::get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())
+}
+
+/// Moves the pinned notes `ids` to the top in the given order. Returns the
+/// pinned notes afterwards.
+pub async fn reorder(pool: &SqlitePool, ids: &[i64]) -> Result<Vec<NoteEntry>, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let current: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM notes WHERE pin_order IS NOT NULL ORDER BY pin_order, id",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch pinned notes: {}", e))?;
+    for (position, id) in reordered(&current, ids).into_iter().enumerate() {
+        sqlx::query("UPDATE notes SET pin_order = ?1 WHERE id = ?2")
+            .bind(position as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to reorder pinned notes: {}", e))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit pinned order: {}", e))?;
+    notes::pinned(pool).await
+}
+
+/// `requested` first, then the other pinned notes in their old order. Ids
+/// that are not pinned are ignored.
+fn reordered(current: &[i64], requested: &[i64]) -> Vec<i64> {
+    let mut order: Vec<i64> = Vec::with_capacity(current.len());
+    for id in requested {
+        if current.contains(id) && !order.contains(id) {
+            order.push(*id);
+        }
+    }
+    order.extend(current.iter().filter(|id| !requested.contains(id)));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requested_notes_move_to_the_top() {
+        assert_eq!(reordered(&[1, 2, 3, 4], &[3, 1]), vec![3, 1, 2, 4]);
+        assert_eq!(reordered(&[1, 2, 3], &[3, 2, 1]), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn unknown_and_repeated_ids_are_ignored() {
+        assert_eq!(reordered(&[1, 2], &[9, 2, 2]), vec![2, 1]);
+        assert_eq!(reordered(&[], &[1]), Vec::<i64>::new());
+    }
+}
@@ -0,0 +1,264 @@
+// Window placement math in physical pixels, kept free of Tauri types so it
+// can be tested without a display.
+
+use serde::{Deserialize, Serialize};
+
+/// Gap between a window and the tray icon or screen edge.
+const MARGIN: i32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn right(&self) -> i64 {
+        self.x as i64 + self.width as i64
+    }
+
+    fn bottom(&self) -> i64 {
+        self.y as i64 + self.height as i64
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x as f64
+            && x < self.right() as f64
+            && y >= self.y as f64
+            && y < self.bottom() as f64
+    }
+
+    /// Area shared with `other`, zero if they don't touch.
+    pub fn overlap(&self, other: &Rect) -> i64 {
+        let width = self.right().min(other.right()) - (self.x as i64).max(other.x as i64);
+        let height = self.bottom().min(other.bottom()) - (self.y as i64).max(other.y as i64);
+        width.max(0) * height.max(0)
+    }
+
+    fn distance_squared(&self, x: f64, y: f64) -> f64 {
+        let dx = (self.x as f64 - x).max(x - self.right() as f64).max(0.0);
+        let dy = (self.y as f64 - y).max(y - self.bottom() as f64).max(0.0);
+        dx * dx + dy * dy
+    }
+}
+
+/// Where a window appears on the chosen monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    Center,
+    TopThird,
+    NearTray,
+}
+
+/// Index of the monitor containing the point, or the closest one if the
+/// point lies in a gap between monitors.
+pub fn monitor_at(monitors: &[Rect], x: f64, y: f64) -> Option<usize> {
+    monitors.iter().position(|m| m.contains(x, y)).or_else(|| {
+        monitors
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(x, y)
+                    .total_cmp(&b.distance_squared(x, y))
+            })
+            .map(|(index, _)| index)
+    })
+}
+
+/// Shrinks and moves `window` so it lies fully inside `area`.
+pub fn clamp(window: Rect, area: Rect) -> Rect {
+    let width = window.width.min(area.width);
+    let height = window.height.min(area.height);
+    let max_x = area.x + (area.width - width) as i32;
+    let max_y = area.y + (area.height - height) as i32;
+    Rect::new(
+        window.x.clamp(area.x, max_x),
+        window.y.clamp(area.y, max_y),
+        width,
+        height,
+    )
+}
+
+/// A monitor's full bounds and the part not covered by menu bar or taskbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Screen {
+    pub bounds: Rect,
+    pub work_area: Rect,
+}
+
+/// Top-left corner for a window of `size` anchored on `screen`.
+///
+/// `tray` is only honoured for `NearTray` when it sits on the same screen;
+/// otherwise the window goes to the top-right corner, where the menu bar
+/// extras live on macOS.
+pub fn anchored_position(
+    screen: &Screen,
+    size: (u32, u32),
+    anchor: Anchor,
+    tray: Option<Rect>,
+) -> (i32, i32) {
+    let area = screen.work_area;
+    let (width, height) = size;
+    let centered_x = area.x + (area.width as i32 - width as i32) / 2;
+
+    let (x, y) = match anchor {
+        Anchor::Center => (
+            centered_x,
+            area.y + (area.height as i32 - height as i32) / 2,
+        ),
+        Anchor::TopThird => (
+            centered_x,
+            area.y + area.height as i32 / 3 - height as i32 / 2,
+        ),
+        Anchor::NearTray => match tray.filter(|t| screen.bounds.overlap(t) > 0) {
+            Some(tray) => {
+                let x = tray.x + tray.width as i32 / 2 - width as i32 / 2;
+                // Taskbars at the bottom (Windows, some Linux panels) open upwards
+                let tray_center_y = tray.y as i64 + tray.height as i64 / 2;
+                let y = if tray_center_y > area.y as i64 + area.height as i64 / 2 {
+                    tray.y - MARGIN - height as i32
+                } else {
+                    tray.bottom() as i32 + MARGIN
+                };
+                (x, y)
+            }
+            None => (area.right() as i32 - MARGIN - width as i32, area.y + MARGIN),
+        },
+    };
+
+    let placed = clamp(Rect::new(x, y, width, height), area);
+    (placed.x, placed.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+    };
+    const RIGHT: Rect = Rect {
+        x: 1920,
+        y: -200,
+        width: 2560,
+        height: 1440,
+    };
+
+    #[test]
+    fn monitor_at_picks_monitor_under_cursor() {
+        let monitors = [LEFT, RIGHT];
+        assert_eq!(monitor_at(&monitors, 100.0, 100.0), Some(0));
+        assert_eq!(monitor_at(&monitors, 2000.0, -100.0), Some(1));
+        assert_eq!(monitor_at(&monitors, 1919.5, 500.0), Some(0));
+    }
+
+    #[test]
+    fn monitor_at_falls_back_to_nearest_monitor() {
+        let monitors = [LEFT, RIGHT];
+        // Below the left monitor, in the dead zone of the taller right one
+        assert_eq!(monitor_at(&monitors, 1800.0, 1150.0), Some(0));
+        assert_eq!(monitor_at(&monitors, 5000.0, 0.0), Some(1));
+        assert_eq!(monitor_at(&[], 0.0, 0.0), None);
+    }
+
+    fn screen(bounds: Rect) -> Screen {
+        Screen {
+            bounds,
+            work_area: bounds,
+        }
+    }
+
+    #[test]
+    fn center_anchor_centers_on_work_area() {
+        assert_eq!(
+            anchored_position(&screen(RIGHT), (680, 440), Anchor::Center, None),
+            (1920 + 940, -200 + 500)
+        );
+    }
+
+    #[test]
+    fn top_third_anchor_centers_window_on_upper_third_line() {
+        assert_eq!(
+            anchored_position(&screen(LEFT), (680, 440), Anchor::TopThird, None),
+            (620, 360 - 220)
+        );
+    }
+
+    #[test]
+    fn top_third_anchor_stays_on_screen_for_tall_windows() {
+        let (_, y) = anchored_position(&screen(LEFT), (400, 1000), Anchor::TopThird, None);
+        assert_eq!(y, 0);
+    }
+
+    #[test]
+    fn near_tray_opens_below_menu_bar_icon() {
+        let menu_bar = Screen {
+            bounds: LEFT,
+            work_area: Rect::new(0, 25, 1920, 1055),
+        };
+        let tray = Rect::new(1500, 0, 24, 24);
+        assert_eq!(
+            anchored_position(&menu_bar, (320, 420), Anchor::NearTray, Some(tray)),
+            (1512 - 160, 24 + MARGIN)
+        );
+    }
+
+    #[test]
+    fn near_tray_opens_above_taskbar_icon() {
+        let taskbar = Screen {
+            bounds: LEFT,
+            work_area: Rect::new(0, 0, 1920, 1040),
+        };
+        let tray = Rect::new(1800, 1040, 40, 40);
+        assert_eq!(
+            anchored_position(&taskbar, (320, 420), Anchor::NearTray, Some(tray)),
+            (1920 - 320, 1040 - MARGIN - 420)
+        );
+    }
+
+    #[test]
+    fn near_tray_on_other_monitor_uses_top_right_corner() {
+        let tray = Rect::new(1500, 0, 24, 24);
+        assert_eq!(
+            anchored_position(&screen(RIGHT), (320, 420), Anchor::NearTray, Some(tray)),
+            (1920 + 2560 - MARGIN - 320, -200 + MARGIN)
+        );
+    }
+
+    #[test]
+    fn clamp_moves_window_back_onto_area() {
+        assert_eq!(
+            clamp(Rect::new(-300, 900, 400, 300), LEFT),
+            Rect::new(0, 780, 400, 300)
+        );
+    }
+
+    #[test]
+    fn clamp_shrinks_windows_larger_than_area() {
+        assert_eq!(
+            clamp(Rect::new(100, 100, 4000, 3000), LEFT),
+            Rect::new(0, 0, 1920, 1080)
+        );
+    }
+
+    #[test]
+    fn overlap_is_zero_for_disjoint_rects() {
+        assert_eq!(LEFT.overlap(&Rect::new(1920, 0, 100, 100)), 0);
+        assert_eq!(LEFT.overlap(&Rect::new(1900, 1000, 100, 100)), 20 * 80);
+    }
+}
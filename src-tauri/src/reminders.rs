@@ -0,0 +1,376 @@
+// Reminders stored in the database. Recurring ones fire once per day at a
+// local time of day, note reminders fire once at `due_at`. Whether one is
+// due is decided from the wall clock on every scheduler tick, so reminders
+// missed during sleep are delivered right after wake-up.
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
+
+use crate::calendar::WorkCalendar;
+use crate::notes;
+
+/// Recurring reminders later than this (laptop asleep all morning) are
+/// skipped for the day instead of nagging after the fact.
+const GRACE_MINUTES: i64 = 180;
+
+const REMINDER_COLUMNS: &str =
+    "id, kind, time_of_day, workdays_only, due_at, note_id, enabled, last_fired_at";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    /// "Write your standup", recurring
+    Standup,
+    /// "Log what you did", recurring
+    EndOfDay,
+    /// One-off reminder attached to a note
+    Note,
+}
+
+impl ReminderKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReminderKind::Standup => "standup",
+            ReminderKind::EndOfDay => "end_of_day",
+            ReminderKind::Note => "note",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<ReminderKind> {
+        match value {
+            "standup" => Some(ReminderKind::Standup),
+            "end_of_day" => Some(ReminderKind::EndOfDay),
+            "note" => Some(ReminderKind::Note),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub kind: ReminderKind,
+    /// `HH:MM` local time for recurring reminders
+    pub time_of_day: Option<String>,
+    pub workdays_only: bool,
+    /// RFC 3339, for note reminders
+    pub due_at: Option<String>,
+    pub note_id: Option<i64>,
+    pub enabled: bool,
+    pub last_fired_at: Option<String>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for Reminder {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let kind: String = row.try_get("kind")?;
+        Ok(Reminder {
+            id: row.try_get("id")?,
+            kind: ReminderKind::parse(&kind).ok_or_else(|| sqlx::Error::ColumnDecode {
+                index: "kind".to_string(),
+                source: format!("unknown reminder kind: {}", kind).into(),
+            })?,
+            time_of_day: row.try_get("time_of_day")?,
+            workdays_only: row.try_get("workdays_only")?,
+            due_at: row.try_get("due_at")?,
+            note_id: row.try_get("note_id")?,
+            enabled: row.try_get("enabled")?,
+            last_fired_at: row.try_get("last_fired_at")?,
+        })
+    }
+}
+
+fn to_local(timestamp: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Local).naive_local())
+}
+
+pub fn parse_time_of_day(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M").ok()
+}
+
+impl Reminder {
+    /// The occurrence that should fire at local time `now`, if any.
+    pub fn pending(&self, now: NaiveDateTime, calendar: &WorkCalendar) -> Option<NaiveDateTime> {
+        if !self.enabled {
+            return None;
+        }
+        let occurrence = match self.kind {
+            // Late note reminders still fire, e.g. when the app was closed
+            ReminderKind::Note => to_local(self.due_at.as_deref()?)?,
+            ReminderKind::Standup | ReminderKind::EndOfDay => {
+                let time = parse_time_of_day(self.time_of_day.as_deref()?)?;
+                let today = now.date();
+                if self.workdays_only && !calendar.is_workday(today) {
+                    return None;
+                }
+                let at = today.and_time(time);
+                if now - at > Duration::minutes(GRACE_MINUTES) {
+                    return None;
+                }
+                at
+            }
+        };
+        if now < occurrence {
+            return None;
+        }
+        match self.last_fired_at.as_deref().and_then(to_local) {
+            Some(last) if last >= occurrence => None,
+            _ => Some(occurrence),
+        }
+    }
+}
+
+pub async fn list(pool: &SqlitePool) -> Result<Vec<Reminder>, String> {
+    sqlx::query_as::<_, Reminder>(&format!(
+        "SELECT {} FROM reminders ORDER BY kind, time_of_day, due_at",
+        REMINDER_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch reminders: {}", e))
+}
+
+pub async fn get(pool: &SqlitePool, id: i64) -> Result<Reminder, String> {
+    sqlx::query_as::<_, Reminder>(&format!(
+        "SELECT {} FROM reminders WHERE id = ?1",
+        REMINDER_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch reminder: {}", e))?
+    .ok_or_else(|| "Reminder not found".to_string())
+}
+
+/// Reminders to fire at local time `now`.
+pub async fn due(
+    pool: &SqlitePool,
+    now: NaiveDateTime,
+    calendar: &WorkCalendar,
+) -> Result<Vec<Reminder>, String> {
+    Ok(list(pool)
+        .await?
+        .into_iter()
+        .filter(|r| r.pending(now, calendar).is_some())
+        .collect())
+}
+
+/// Records delivery. One-off reminders are disabled afterwards.
+pub async fn mark_fired(pool: &SqlitePool, id: i64, at: &DateTime<Utc>) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE reminders SET last_fired_at = ?1,
+         enabled = CASE kind WHEN 'note' THEN 0 ELSE enabled END
+         WHERE id = ?2",
+    )
+    .bind(notes::timestamp(at))
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to update reminder: {}", e))?;
+    Ok(())
+}
+
+pub async fn add_note_reminder(
+    pool: &SqlitePool,
+    note_id: i64,
+    due_at: &DateTime<Utc>,
+) -> Result<Reminder, String> {
+    let id = sqlx::query(
+        "INSERT INTO reminders (kind, workdays_only, due_at, note_id, enabled)
+         VALUES ('note', 0, ?1, ?2, 1)",
+    )
+    .bind(notes::timestamp(due_at))
+    .bind(note_id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to create reminder: {}", e))?
+    .last_insert_rowid();
+    get(pool, id).await
+}
+
+/// Changes time and schedule of a recurring reminder.
+pub async fn update_recurring(
+    pool: &SqlitePool,
+    id: i64,
+    time_of_day: &str,
+    workdays_only: bool,
+    enabled: bool,
+) -> Result<Reminder, String> {
+    let time = parse_time_of_day(time_of_day)
+        .ok_or_else(|| format!("Invalid time of day: {}", time_of_day))?;
+    sqlx::query(
+        "UPDATE reminders SET time_of_day = ?1, workdays_only = ?2, enabled = ?3
+         WHERE id = ?4 AND kind != 'note'",
+    )
+    .bind(time.format("%H:%M").to_string())
+    .bind(workdays_only)
+    .bind(enabled)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to update reminder: {}", e))?;
+    get(pool, id).await
+}
+
+/// Reminders of note `id`, e.g. before it is deleted.
+pub async fn of_note(conn: &mut SqliteConnection, id: i64) -> Result<Vec<Reminder>, String> {
+    sqlx::query_as::<_, Reminder>(&format!(
+        "SELECT {} FROM reminders WHERE note_id = ?1 ORDER BY id",
+        REMINDER_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to fetch reminders: {}", e))
+}
+
+/// Writes a deleted reminder back under its old id.
+pub async fn reinsert(conn: &mut SqliteConnection, reminder: &Reminder) -> Result<(), String> {
+    sqlx::query(&format!(
+        "INSERT OR IGNORE INTO reminders ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        REMINDER_COLUMNS
+    ))
+    .bind(reminder.id)
+    .bind(reminder.kind.as_str())
+    .bind(&reminder.time_of_day)
+    .bind(reminder.workdays_only)
+    .bind(&reminder.due_at)
+    .bind(reminder.note_id)
+    .bind(reminder.enabled)
+    .bind(&reminder.last_fired_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to restore reminder: {}", e))?;
+    Ok(())
+}
+
+pub async fn delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM reminders WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to delete reminder: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // October 2026, the 19th is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn stamp(local: NaiveDateTime) -> String {
+        let utc = Local
+            .from_local_datetime(&local)
+            .unwrap()
+            .with_timezone(&Utc);
+        notes::timestamp(&utc)
+    }
+
+    fn standup() -> Reminder {
+        Reminder {
+            id: 1,
+            kind: ReminderKind::Standup,
+            time_of_day: Some("09:15".to_string()),
+            workdays_only: true,
+            due_at: None,
+            note_id: None,
+            enabled: true,
+            last_fired_at: None,
+        }
+    }
+
+    #[test]
+    fn recurring_fires_once_the_time_has_passed() {
+        let calendar = WorkCalendar::default();
+        assert_eq!(standup().pending(at(19, 9, 0), &calendar), None);
+        assert_eq!(
+            standup().pending(at(19, 9, 15), &calendar),
+            Some(at(19, 9, 15))
+        );
+    }
+
+    #[test]
+    fn recurring_fires_only_once_per_day() {
+        let calendar = WorkCalendar::default();
+        let fired_today = Reminder {
+            last_fired_at: Some(stamp(at(19, 9, 16))),
+            ..standup()
+        };
+        assert_eq!(fired_today.pending(at(19, 10, 0), &calendar), None);
+
+        let fired_yesterday = Reminder {
+            last_fired_at: Some(stamp(at(16, 9, 15))),
+            ..standup()
+        };
+        assert!(fired_yesterday.pending(at(19, 10, 0), &calendar).is_some());
+    }
+
+    #[test]
+    fn missed_recurring_fires_after_wake_within_grace() {
+        let calendar = WorkCalendar::default();
+        assert!(standup().pending(at(19, 11, 0), &calendar).is_some());
+        assert_eq!(standup().pending(at(19, 13, 0), &calendar), None);
+    }
+
+    #[test]
+    fn workday_only_reminders_skip_weekends_and_holidays() {
+        let calendar = WorkCalendar {
+            holidays: vec![NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()],
+            ..WorkCalendar::default()
+        };
+        assert_eq!(standup().pending(at(18, 9, 30), &calendar), None);
+        assert_eq!(standup().pending(at(20, 9, 30), &calendar), None);
+
+        let every_day = Reminder {
+            workdays_only: false,
+            ..standup()
+        };
+        assert!(every_day.pending(at(18, 9, 30), &calendar).is_some());
+    }
+
+    #[test]
+    fn disabled_reminders_never_fire() {
+        let disabled = Reminder {
+            enabled: false,
+            ..standup()
+        };
+        assert_eq!(
+            disabled.pending(at(19, 9, 30), &WorkCalendar::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn note_reminders_fire_late_but_once() {
+        let calendar = WorkCalendar::default();
+        let reminder = Reminder {
+            kind: ReminderKind::Note,
+            time_of_day: None,
+            due_at: Some(stamp(at(17, 14, 0))),
+            note_id: Some(7),
+            ..standup()
+        };
+        assert_eq!(reminder.pending(at(17, 13, 0), &calendar), None);
+        assert_eq!(
+            reminder.pending(at(19, 8, 0), &calendar),
+            Some(at(17, 14, 0))
+        );
+
+        let fired = Reminder {
+            last_fired_at: Some(stamp(at(19, 8, 0))),
+            ..reminder
+        };
+        assert_eq!(fired.pending(at(19, 8, 1), &calendar), None);
+    }
+}
@@ -0,0 +1,420 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::attachments::Attachment;
+use crate::days;
+use crate::i18n::{self, Locale, Msg};
+use crate::notes::{NoteEntry, Section};
+use crate::tasks::{self, TaskState};
+
+/// Plain-text standup for one day, ready to paste into chat. Attachments
+/// are listed below their note by the name they get on export.
+pub fn standup(
+    locale: Locale,
+    day: NaiveDate,
+    notes: &[NoteEntry],
+    attachments: &[Attachment],
+) -> String {
+    let date = i18n::format_date(locale, day);
+    let mut text = i18n::t_with(locale, Msg::StandupHeading, &[("date", &date)]);
+    text.push('\n');
+    for note in notes {
+        // Tasks are exported as markdown checkboxes
+        let content = tasks::to_markdown(note.task_state, note.content.trim());
+        for (i, line) in content.lines().enumerate() {
+            if i == 0 {
+                text.push_str(&format!("\n- {}", line));
+            } else {
+                text.push_str(&format!("\n  {}", line));
+            }
+        }
+        for attachment in attachments.iter().filter(|a| a.note_id == note.id) {
+            text.push_str(&format!("\n  📎 {}", attachment.export_name()));
+        }
+    }
+    text
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayCount {
+    pub day: NaiveDate,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionCount {
+    pub section: Section,
+    pub count: usize,
+}
+
+/// A blocker noted on more than one day.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecurringBlocker {
+    pub content: String,
+    pub days: Vec<NaiveDate>,
+}
+
+/// Notes of a date range aggregated for retros and sprint reviews.
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total: usize,
+    /// Every day of the range, including days without notes
+    pub by_day: Vec<DayCount>,
+    /// Most used first
+    pub by_tag: Vec<TagCount>,
+    pub by_section: Vec<SectionCount>,
+    pub done_tasks: Vec<NoteEntry>,
+    pub recurring_blockers: Vec<RecurringBlocker>,
+    pub untagged: Vec<NoteEntry>,
+}
+
+/// Longest range a summary covers, every note in it is loaded at once.
+pub const MAX_SUMMARY_DAYS: i64 = 366;
+
+/// Rejects reversed ranges and ranges longer than `MAX_SUMMARY_DAYS`.
+pub fn check_range(from: NaiveDate, to: NaiveDate) -> Result<(), String> {
+    if from > to {
+        return Err("Start date is after end date".to_string());
+    }
+    if (to - from).num_days() >= MAX_SUMMARY_DAYS {
+        return Err(format!("Summaries cover at most {} days", MAX_SUMMARY_DAYS));
+    }
+    Ok(())
+}
+
+/// Aggregates `notes` of the days `from` to `to`.
+pub fn summarize(from: NaiveDate, to: NaiveDate, notes: &[NoteEntry]) -> Summary {
+    let mut per_day: BTreeMap<NaiveDate, usize> =
+        days::each_day(from, to).map(|day| (day, 0)).collect();
+    let mut per_tag: HashMap<&str, usize> = HashMap::new();
+    let mut per_section: HashMap<Section, usize> = HashMap::new();
+    // Keyed by normalized content, first spelling wins
+    let mut blockers: Vec<(String, RecurringBlocker)> = Vec::new();
+
+    for note in notes {
+        let day = days::local_day_of(&note.created_at);
+        if let Some(count) = day.and_then(|d| per_day.get_mut(&d)) {
+            *count += 1;
+        }
+        for tag in &note.tags {
+            *per_tag.entry(tag.as_str()).or_default() += 1;
+        }
+        // Notes without a section count as done work, like in the standup
+        let section = note.section.unwrap_or(Section::Done);
+        *per_section.entry(section).or_default() += 1;
+
+        if let (Section::Blocker, Some(day)) = (section, day) {
+            let key = note.content.trim().to_lowercase();
+            match blockers.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, blocker)) if !blocker.days.contains(&day) => blocker.days.push(day),
+                Some(_) => {}
+                None => blockers.push((
+                    key,
+                    RecurringBlocker {
+                        content: note.content.trim().to_string(),
+                        days: vec![day],
+                    },
+                )),
+            }
+        }
+    }
+
+    let mut by_tag: Vec<TagCount> = per_tag
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag: tag.to_string(),
+            count,
+        })
+        .collect();
+    by_tag.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    let mut recurring_blockers: Vec<RecurringBlocker> = blockers
+        .into_iter()
+        .map(|(_, blocker)| blocker)
+        .filter(|blocker| blocker.days.len() > 1)
+        .collect();
+    recurring_blockers.sort_by_key(|b| std::cmp::Reverse(b.days.len()));
+
+    Summary {
+        from,
+        to,
+        total: notes.len(),
+        by_day: per_day
+            .into_iter()
+            .map(|(day, count)| DayCount { day, count })
+            .collect(),
+        by_tag,
+        by_section: [Section::Done, Section::Todo, Section::Blocker]
+            .into_iter()
+            .filter_map(|section| {
+                per_section.get(&section).map(|count| SectionCount {
+                    section,
+                    count: *count,
+                })
+            })
+            .collect(),
+        done_tasks: notes
+            .iter()
+            .filter(|n| n.task_state == Some(TaskState::Done))
+            .cloned()
+            .collect(),
+        recurring_blockers,
+        untagged: notes
+            .iter()
+            .filter(|n| n.tags.is_empty())
+            .cloned()
+            .collect(),
+    }
+}
+
+fn section_label(locale: Locale, section: Section) -> &'static str {
+    match section {
+        Section::Done => i18n::t(locale, Msg::SectionDone),
+        Section::Todo => i18n::t(locale, Msg::SectionTodo),
+        Section::Blocker => i18n::t(locale, Msg::SectionBlocker),
+    }
+}
+
+fn push_heading(text: &mut String, heading: &str) {
+    text.push_str(&format!("\n\n{}:", heading));
+}
+
+/// Plain-text rendering of a summary, in the same style as the standup.
+pub fn render_summary(locale: Locale, summary: &Summary) -> String {
+    let from = i18n::format_date(locale, summary.from);
+    let to = i18n::format_date(locale, summary.to);
+    let mut text = i18n::t_with(locale, Msg::SummaryHeading, &[("from", &from), ("to", &to)]);
+    text.push('\n');
+    text.push_str(&i18n::t_with(
+        locale,
+        Msg::SummaryTotal,
+        &[("count", &summary.total.to_string())],
+    ));
+
+    push_heading(&mut text, i18n::t(locale, Msg::SummaryByDay));
+    for entry in summary.by_day.iter().filter(|d| d.count > 0) {
+        let label = i18n::format_day_label(locale, entry.day);
+        text.push_str(&format!("\n- {}: {}", label, entry.count));
+    }
+
+    if !summary.by_tag.is_empty() {
+        push_heading(&mut text, i18n::t(locale, Msg::SummaryByTag));
+        for entry in &summary.by_tag {
+            text.push_str(&format!("\n- #{}: {}", entry.tag, entry.count));
+        }
+    }
+
+    push_heading(&mut text, i18n::t(locale, Msg::SummaryBySection));
+    for entry in &summary.by_section {
+        let label = section_label(locale, entry.section);
+        text.push_str(&format!("\n- {}: {}", label, entry.count));
+    }
+
+    let sections: [(Msg, Vec<String>); 3] = [
+        (
+            Msg::SummaryDoneTasks,
+            summary
+                .done_tasks
+                .iter()
+                .map(|n| tasks::to_markdown(n.task_state, n.content.trim()))
+                .collect(),
+        ),
+        (
+            Msg::SummaryRecurringBlockers,
+            summary
+                .recurring_blockers
+                .iter()
+                .map(|b| {
+                    let days: Vec<String> = b
+                        .days
+                        .iter()
+                        .map(|d| i18n::format_day_label(locale, *d))
+                        .collect();
+                    format!("{} ({})", b.content, days.join("; "))
+                })
+                .collect(),
+        ),
+        (
+            Msg::SummaryUntagged,
+            summary
+                .untagged
+                .iter()
+                .map(|n| n.content.trim().to_string())
+                .collect(),
+        ),
+    ];
+    for (heading, lines) in sections {
+        if lines.is_empty() {
+            continue;
+        }
+        push_heading(&mut text, i18n::t(locale, heading));
+        for line in lines {
+            // Only the first line of multi-line notes, summaries stay scannable
+            let first = line.lines().next().unwrap_or_default();
+            text.push_str(&format!("\n- {}", first));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes;
+    use chrono::Duration;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn note(id: i64, on: u32, content: &str, section: Option<Section>, tags: &[&str]) -> NoteEntry {
+        let created_at = notes::timestamp(&(days::start_of_day(day(on)) + Duration::hours(10)));
+        NoteEntry {
+            id,
+            content: content.to_string(),
+            created_at: created_at.clone(),
+            updated_at: created_at,
+            is_quick_capture: false,
+            section,
+            priority: None,
+            duration_minutes: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            task_state: None,
+            completed_at: None,
+            processed_at: None,
+            pin_order: None,
+            starred: false,
+            archived_at: None,
+        }
+    }
+
+    fn sample() -> Vec<NoteEntry> {
+        vec![
+            note(1, 12, "Review PR", None, &["backend"]),
+            note(2, 12, "Waiting for VPN access", Some(Section::Blocker), &[]),
+            note(
+                3,
+                13,
+                "waiting for VPN access ",
+                Some(Section::Blocker),
+                &["infra"],
+            ),
+            note(
+                4,
+                14,
+                "Plan release",
+                Some(Section::Todo),
+                &["backend", "release"],
+            ),
+            NoteEntry {
+                task_state: Some(TaskState::Done),
+                ..note(5, 14, "Fix login", None, &[])
+            },
+        ]
+    }
+
+    #[test]
+    fn summary_counts_by_day_tag_and_section() {
+        let summary = summarize(day(12), day(18), &sample());
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.by_day.len(), 7);
+        let counts: Vec<usize> = summary.by_day.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![2, 1, 2, 0, 0, 0, 0]);
+
+        let tags: Vec<(&str, usize)> = summary
+            .by_tag
+            .iter()
+            .map(|t| (t.tag.as_str(), t.count))
+            .collect();
+        assert_eq!(tags, vec![("backend", 2), ("infra", 1), ("release", 1)]);
+
+        let sections: Vec<(Section, usize)> = summary
+            .by_section
+            .iter()
+            .map(|s| (s.section, s.count))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (Section::Done, 2),
+                (Section::Todo, 1),
+                (Section::Blocker, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_lists_done_tasks_untagged_notes_and_recurring_blockers() {
+        let summary = summarize(day(12), day(18), &sample());
+        let done: Vec<i64> = summary.done_tasks.iter().map(|n| n.id).collect();
+        assert_eq!(done, vec![5]);
+        let untagged: Vec<i64> = summary.untagged.iter().map(|n| n.id).collect();
+        assert_eq!(untagged, vec![2, 5]);
+
+        assert_eq!(summary.recurring_blockers.len(), 1);
+        let blocker = &summary.recurring_blockers[0];
+        assert_eq!(blocker.content, "Waiting for VPN access");
+        assert_eq!(blocker.days, vec![day(12), day(13)]);
+    }
+
+    #[test]
+    fn blockers_on_a_single_day_do_not_recur() {
+        let notes = vec![
+            note(1, 12, "Flaky CI", Some(Section::Blocker), &[]),
+            note(2, 12, "Flaky CI", Some(Section::Blocker), &[]),
+        ];
+        assert!(summarize(day(12), day(12), &notes)
+            .recurring_blockers
+            .is_empty());
+    }
+
+    #[test]
+    fn rendered_summary_uses_locale_templates() {
+        let summary = summarize(day(12), day(18), &sample());
+        let text = render_summary(Locale::En, &summary);
+        assert!(text.starts_with("Summary 10/12/2026 – 10/18/2026\n5 notes"));
+        assert!(text.contains("\n\nBy tag:\n- #backend: 2"));
+        assert!(text.contains("\n\nDone tasks:\n- [x] Fix login"));
+        assert!(text.contains("Waiting for VPN access (Mon, 10/12; Tue, 10/13)"));
+        assert!(!text.contains("Thu, 10/15"));
+    }
+
+    #[test]
+    fn standup_lists_attachments_below_their_note() {
+        let notes = vec![
+            note(1, 19, "Deploy failed", Some(Section::Blocker), &[]),
+            note(2, 19, "Pairing", None, &[]),
+        ];
+        let log = Attachment {
+            id: 7,
+            note_id: 1,
+            name: "deploy.log".to_string(),
+            hash: "ab".repeat(32),
+            size: 120,
+            created_at: notes[0].created_at.clone(),
+        };
+        assert_eq!(
+            standup(Locale::En, day(19), &notes, &[log]),
+            "Standup for 10/19/2026\n\n- Deploy failed\n  📎 7-deploy.log\n- Pairing"
+        );
+    }
+
+    #[test]
+    fn summary_ranges_are_limited_to_a_year() {
+        // 366 days, enough for a leap year
+        let start = NaiveDate::from_ymd_opt(2025, 10, 19).unwrap();
+        assert!(check_range(day(19), day(19)).is_ok());
+        assert!(check_range(start, day(19)).is_ok());
+        assert!(check_range(start - Duration::days(1), day(19)).is_err());
+        assert!(check_range(day(20), day(19)).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+// How many quick captures the quick capture window lists, and what happens
+// to old ones. Pinned and starred captures are always kept, so are captures
+// filed from the inbox, which are regular notes by now, and open tasks.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::notes::{self, NoteEntry, NOTE_COLUMNS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    Keep,
+    Archive,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureRetention {
+    /// Quick capture lists notes of this many past hours
+    pub visible_hours: u32,
+    pub visible_limit: u32,
+    /// Applied to quick captures older than `after_days`
+    pub action: RetentionAction,
+    pub after_days: u32,
+}
+
+impl Default for CaptureRetention {
+    fn default() -> Self {
+        CaptureRetention {
+            visible_hours: 48,
+            visible_limit: 50,
+            action: RetentionAction::Keep,
+            after_days: 90,
+        }
+    }
+}
+
+impl CaptureRetention {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=720).contains(&self.visible_hours) {
+            return Err(format!("Invalid visible window: {}", self.visible_hours));
+        }
+        if !(1..=500).contains(&self.visible_limit) {
+            return Err(format!("Invalid row limit: {}", self.visible_limit));
+        }
+        if !(1..=3650).contains(&self.after_days) {
+            return Err(format!("Invalid retention age: {}", self.after_days));
+        }
+        Ok(())
+    }
+
+    pub fn visible_since(&self, now: &DateTime<Utc>) -> DateTime<Utc> {
+        *now - Duration::hours(i64::from(self.visible_hours))
+    }
+
+    /// Captures created before this expire, `None` when they are kept.
+    fn cutoff(&self, now: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.action != RetentionAction::Keep)
+            .then(|| *now - Duration::days(i64::from(self.after_days)))
+    }
+}
+
+/// What applying a retention setting would do right now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetentionPreview {
+    /// Notes quick capture would list
+    pub visible: usize,
+    /// Captures that would be archived or deleted
+    pub affected: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Expired {
+    /// Archived captures afterwards
+    pub archived: Vec<NoteEntry>,
+    pub deleted: Vec<NoteEntry>,
+    /// Notes whose links to deleted captures were rewritten, afterwards
+    pub relinked: Vec<NoteEntry>,
+}
+
+fn expired_where(action: RetentionAction) -> &'static str {
+    match action {
+        // Archived captures still get deleted once deletion is configured
+        RetentionAction::Delete => {
+            "is_quick_capture = 1 AND processed_at IS NULL AND pin_order IS NULL \
+             AND NOT starred AND task_state IS NOT 'open' AND created_at < ?1"
+        }
+        _ => {
+            "is_quick_capture = 1 AND processed_at IS NULL AND pin_order IS NULL \
+             AND NOT starred AND task_state IS NOT 'open' AND created_at < ?1 \
+             AND archived_at IS NULL"
+        }
+    }
+}
+
+pub async fn preview(
+    pool: &SqlitePool,
+    retention: &CaptureRetention,
+    now: &DateTime<Utc>,
+) -> Result<RetentionPreview, String> {
+    let visible = notes::recent_notes(pool, &retention.visible_since(now), retention.visible_limit)
+        .await?
+        .len();
+    let affected: i64 = match retention.cutoff(now) {
+        Some(cutoff) => sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM notes WHERE {}",
+            expired_where(retention.action)
+        ))
+        .bind(notes::timestamp(&cutoff))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to count expired captures: {}", e))?,
+        None => 0,
+    };
+    Ok(RetentionPreview {
+        visible,
+        affected: usize::try_from(affected).unwrap_or_default(),
+    })
+}
+
+/// Archives or deletes the quick captures `retention` expires.
+pub async fn enforce(
+    pool: &SqlitePool,
+    retention: &CaptureRetention,
+    now: &DateTime<Utc>,
+) -> Result<Expired, String> {
+    let Some(cutoff) = retention.cutoff(now) else {
+        return Ok(Expired::default());
+    };
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let expired = sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE {}",
+        NOTE_COLUMNS,
+        expired_where(retention.action)
+    ))
+    .bind(notes::timestamp(&cutoff))
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch expired captures: {}", e))?;
+
+    let now = notes::timestamp(now);
+    let mut result = Expired::default();
+    for note in expired {
+        if retention.action == RetentionAction::Archive {
+            let mut after = note;
+            after.archived_at = Some(now.clone());
+            notes::restore(&mut tx, after.id, Some(&after)).await?;
+            result.archived.push(after);
+        } else {
+            for (_, after) in notes::unlink(&mut tx, &note, &now).await? {
+                result.relinked.push(after);
+            }
+            notes::restore(&mut tx, note.id, None).await?;
+            result.deleted.push(note);
+        }
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit retention: {}", e))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::TaskState;
+    use crate::{db, inbox};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-19T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn defaults_match_the_old_fixed_window() {
+        let retention = CaptureRetention::default();
+        assert_eq!((retention.visible_hours, retention.visible_limit), (48, 50));
+        assert_eq!(retention.cutoff(&now()), None);
+        assert!(retention.validate().is_ok());
+    }
+
+    #[test]
+    fn cutoff_is_the_configured_age() {
+        let retention = CaptureRetention {
+            action: RetentionAction::Archive,
+            after_days: 30,
+            ..CaptureRetention::default()
+        };
+        assert_eq!(retention.cutoff(&now()), Some(now() - Duration::days(30)));
+        assert_eq!(retention.visible_since(&now()), now() - Duration::hours(48));
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        for retention in [
+            CaptureRetention {
+                visible_hours: 0,
+                ..CaptureRetention::default()
+            },
+            CaptureRetention {
+                visible_limit: 501,
+                ..CaptureRetention::default()
+            },
+            CaptureRetention {
+                after_days: 0,
+                ..CaptureRetention::default()
+            },
+        ] {
+            assert!(retention.validate().is_err());
+        }
+    }
+
+    async fn capture(pool: &SqlitePool, content: &str, created_at: DateTime<Utc>) -> NoteEntry {
+        let mut note = notes::NewNote::text(content, created_at);
+        note.is_quick_capture = true;
+        notes::insert_note(pool, &note).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn filed_captures_and_open_tasks_survive() {
+        let pool = db::test_pool().await;
+        let old = now() - Duration::days(100);
+        let expired = capture(&pool, "expired", old).await;
+        let filed = capture(&pool, "filed", old).await;
+        let file = inbox::InboxAction::File {
+            day: None,
+            section: None,
+        };
+        inbox::process(&pool, filed.id, &file).await.unwrap();
+        let mut task = notes::NewNote::text("open task", old);
+        task.is_quick_capture = true;
+        task.task_state = Some(TaskState::Open);
+        let task = notes::insert_note(&pool, &task).await.unwrap();
+
+        let retention = CaptureRetention {
+            action: RetentionAction::Delete,
+            ..CaptureRetention::default()
+        };
+        assert_eq!(
+            preview(&pool, &retention, &now()).await.unwrap().affected,
+            1
+        );
+        let result = enforce(&pool, &retention, &now()).await.unwrap();
+        let deleted: Vec<i64> = result.deleted.iter().map(|n| n.id).collect();
+        assert_eq!(deleted, vec![expired.id]);
+        for id in [filed.id, task.id] {
+            assert!(notes::get_note(&pool, id).await.unwrap().is_some());
+        }
+    }
+}
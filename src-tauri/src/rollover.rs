@@ -0,0 +1,70 @@
+use chrono::NaiveDate;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::{attachments, days, db, events, history, tray};
+
+// Polling the wall clock catches sleep/wake and timezone changes, which a
+// single sleep until midnight would miss
+const TICK: Duration = Duration::from_secs(20);
+
+/// Last day the app has seen as "today".
+pub type CurrentDay = Mutex<NaiveDate>;
+
+/// Starts the background thread that notices when the local day changes.
+pub fn start(app: &AppHandle) {
+    app.manage(CurrentDay::new(days::today()));
+    let app = app.clone();
+    thread::spawn(move || loop {
+        if let Err(e) = tauri::async_runtime::block_on(check(&app)) {
+            eprintln!("Day rollover failed: {}", e);
+        }
+        thread::sleep(TICK);
+    });
+}
+
+/// Runs the rollover hooks if today differs from the last seen day, e.g.
+/// after midnight or when the day start setting changed.
+pub async fn check(app: &AppHandle) -> Result<(), String> {
+    let today = days::today();
+    {
+        let state = app.state::<CurrentDay>();
+        let mut current = state
+            .lock()
+            .map_err(|e| format!("Day lock poisoned: {}", e))?;
+        if *current == today {
+            return Ok(());
+        }
+        *current = today;
+    }
+    on_day_changed(app, today).await
+}
+
+async fn on_day_changed(app: &AppHandle, today: NaiveDate) -> Result<(), String> {
+    events::day_changed(app, today);
+
+    // Open tasks carry over when the day is read, the tray only needs a rebuild
+    tray::refresh(app).await?;
+
+    let pool = db::pool(app).await?;
+    sqlx::query("PRAGMA optimize")
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to optimize database: {}", e))?;
+    collect_attachment_garbage(app).await?;
+    Ok(())
+}
+
+/// Removes attachment files no note refers to, except those of deleted notes
+/// that undo can still bring back.
+pub async fn collect_attachment_garbage(app: &AppHandle) -> Result<usize, String> {
+    let pool = db::pool(app).await?;
+    let keep = app
+        .state::<history::HistoryState>()
+        .lock()
+        .map_err(|e| format!("History lock poisoned: {}", e))?
+        .attachment_hashes();
+    attachments::collect_garbage(&pool, &db::attachments_dir(app)?, &keep).await
+}
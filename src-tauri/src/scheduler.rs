@@ -0,0 +1,207 @@
+use chrono::{Local, Utc};
+use notify_rust::NotificationResponse;
+use sqlx::SqlitePool;
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::events::{self, ChangeKind};
+use crate::i18n::{self, Msg};
+use crate::reminders::{self, Reminder, ReminderKind};
+use crate::{archive, db, inbox, natural_date, notes, retention, settings, windows};
+
+// Short enough that reminders arrive promptly after the machine wakes up,
+// since a sleeping thread does not notice suspend
+const TICK: Duration = Duration::from_secs(30);
+
+/// Starts the background thread that fires due reminders, files stale
+/// quick captures, applies the capture retention and archives old notes.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        if let Err(e) = tauri::async_runtime::block_on(tick(&app)) {
+            eprintln!("Failed to run background jobs: {}", e);
+        }
+        thread::sleep(TICK);
+    });
+}
+
+// Each job runs on its own, one that fails must not hold up the others
+async fn tick(app: &AppHandle) -> Result<(), String> {
+    let pool = db::pool(app).await?;
+    if let Err(e) = fire_reminders(app, &pool).await {
+        eprintln!("Failed to fire reminders: {}", e);
+    }
+    if let Err(e) = file_stale_captures(app, &pool).await {
+        eprintln!("Failed to file stale captures: {}", e);
+    }
+    if let Err(e) = expire_captures(app, &pool).await {
+        eprintln!("Failed to expire captures: {}", e);
+    }
+    if let Err(e) = archive_old_notes(app, &pool).await {
+        eprintln!("Failed to archive old notes: {}", e);
+    }
+    Ok(())
+}
+
+async fn fire_reminders(app: &AppHandle, pool: &SqlitePool) -> Result<(), String> {
+    let calendar = settings::current(app).work_calendar;
+    for reminder in reminders::due(pool, Local::now().naive_local(), &calendar).await? {
+        // Marked as fired either way, a notification that can't be shown
+        // would otherwise be retried every tick
+        if let Err(e) = notify(app, pool, &reminder).await {
+            eprintln!("Failed to show reminder {}: {}", reminder.id, e);
+        }
+        if let Err(e) = reminders::mark_fired(pool, reminder.id, &Utc::now()).await {
+            eprintln!("Failed to mark reminder {} as fired: {}", reminder.id, e);
+        }
+    }
+    Ok(())
+}
+
+async fn file_stale_captures(app: &AppHandle, pool: &SqlitePool) -> Result<(), String> {
+    let Some(hours) = settings::current(app).inbox_auto_file_hours else {
+        return Ok(());
+    };
+    let cutoff = Utc::now() - chrono::Duration::hours(i64::from(hours));
+    let filed = inbox::auto_file(pool, &cutoff).await?;
+    if !filed.is_empty() {
+        let changed: Vec<&notes::NoteEntry> = filed.iter().map(|(_, after)| after).collect();
+        events::notes_changed(app, ChangeKind::Updated, &changed);
+    }
+    Ok(())
+}
+
+async fn expire_captures(app: &AppHandle, pool: &SqlitePool) -> Result<(), String> {
+    let retention = settings::current(app).capture_retention;
+    let expired = retention::enforce(pool, &retention, &Utc::now()).await?;
+    if !expired.deleted.is_empty() {
+        let deleted: Vec<&notes::NoteEntry> = expired.deleted.iter().collect();
+        events::notes_changed(app, ChangeKind::Deleted, &deleted);
+    }
+    let updated: Vec<&notes::NoteEntry> =
+        expired.archived.iter().chain(&expired.relinked).collect();
+    if !updated.is_empty() {
+        events::notes_changed(app, ChangeKind::Updated, &updated);
+    }
+    Ok(())
+}
+
+async fn archive_old_notes(app: &AppHandle, pool: &SqlitePool) -> Result<(), String> {
+    let policy = settings::current(app).archive;
+    let archived = archive::archive_old(pool, &policy, &Utc::now()).await?;
+    if !archived.is_empty() {
+        let changed: Vec<&notes::NoteEntry> = archived.iter().map(|(_, after)| after).collect();
+        events::notes_changed(app, ChangeKind::Updated, &changed);
+    }
+    Ok(())
+}
+
+async fn notify(app: &AppHandle, pool: &SqlitePool, reminder: &Reminder) -> Result<(), String> {
+    let locale = settings::locale(app);
+    let (title, body) = match reminder.kind {
+        ReminderKind::Standup => (
+            i18n::t(locale, Msg::ReminderStandupTitle),
+            i18n::t(locale, Msg::ReminderStandupBody).to_string(),
+        ),
+        ReminderKind::EndOfDay => (
+            i18n::t(locale, Msg::ReminderEndOfDayTitle),
+            i18n::t(locale, Msg::ReminderEndOfDayBody).to_string(),
+        ),
+        ReminderKind::Note => {
+            let note = match reminder.note_id {
+                Some(id) => notes::get_note(pool, id).await?,
+                None => None,
+            };
+            (
+                i18n::t(locale, Msg::ReminderNoteTitle),
+                note.map(|n| n.content).unwrap_or_default(),
+            )
+        }
+    };
+    show_notification(app, title, &body, i18n::t(locale, Msg::ReminderOpen))
+}
+
+/// Shows a notification that opens quick capture when clicked. Goes through
+/// notify-rust directly, the notification plugin drops the handle that
+/// reports clicks on desktop.
+fn show_notification(app: &AppHandle, title: &str, body: &str, open: &str) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(title)
+        .body(body)
+        .auto_icon()
+        // Linux only reports clicks on notifications with a default action,
+        // macOS and Windows show it as a button
+        .action("default", open);
+    // Same app identity as the plugin uses, dev builds have none of their own
+    #[cfg(target_os = "macos")]
+    {
+        let identifier = &app.config().identifier;
+        let _ = notify_rust::set_application(if tauri::is_dev() {
+            "com.apple.Terminal"
+        } else {
+            identifier
+        });
+    }
+    #[cfg(windows)]
+    if !tauri::is_dev() {
+        notification.app_id(&app.config().identifier);
+    }
+    let handle = notification
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+
+    // Waiting blocks until the notification is clicked or dismissed
+    let app = app.clone();
+    thread::spawn(move || {
+        let result = handle.wait_for_response(|response: &NotificationResponse| {
+            if let NotificationResponse::Default | NotificationResponse::Action(_) = response {
+                let main = app.clone();
+                if let Err(e) = app.run_on_main_thread(move || windows::show_quick_capture(&main)) {
+                    eprintln!("Failed to open quick capture: {}", e);
+                }
+            }
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to wait for notification: {}", e);
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_reminders(app: AppHandle) -> Result<Vec<Reminder>, String> {
+    let pool = db::pool(&app).await?;
+    reminders::list(&pool).await
+}
+
+#[tauri::command]
+pub async fn update_reminder(
+    app: AppHandle,
+    id: i64,
+    time_of_day: String,
+    workdays_only: bool,
+    enabled: bool,
+) -> Result<Reminder, String> {
+    let pool = db::pool(&app).await?;
+    reminders::update_recurring(&pool, id, &time_of_day, workdays_only, enabled).await
+}
+
+// `at` takes RFC 3339 or natural language like "morgen 9 Uhr"
+#[tauri::command]
+pub async fn add_note_reminder(
+    app: AppHandle,
+    note_id: i64,
+    at: String,
+) -> Result<Reminder, String> {
+    let due_at = natural_date::resolve(&at)?;
+    let pool = db::pool(&app).await?;
+    reminders::add_note_reminder(&pool, note_id, &due_at).await
+}
+
+#[tauri::command]
+pub async fn delete_reminder(app: AppHandle, id: i64) -> Result<(), String> {
+    let pool = db::pool(&app).await?;
+    reminders::delete(&pool, id).await
+}
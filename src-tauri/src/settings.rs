@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::archive::ArchivePolicy;
+use crate::calendar::{SprintConfig, WorkCalendar};
+use crate::i18n::{self, Locale};
+use crate::retention::CaptureRetention;
+use crate::window_state::WindowPlacement;
+use crate::{autostart, days, rollover, tray, windows};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// Persisted user preferences, stored as JSON in the app config directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// `None` follows the OS language
+    pub language: Option<Locale>,
+    /// Keyed by window label, see `WindowPlacement::default_for` for defaults
+    pub window_placement: HashMap<String, WindowPlacement>,
+    pub work_calendar: WorkCalendar,
+    /// Local hour at which a new day begins, 0 is midnight
+    pub day_start_hour: u32,
+    pub sprint: SprintConfig,
+    /// Quick captures older than this are filed automatically, `None` keeps
+    /// them in the inbox
+    pub inbox_auto_file_hours: Option<u32>,
+    /// Clipboard captures are saved without asking first
+    pub clipboard_capture_silent: bool,
+    /// Page titles of captured links are fetched from the web
+    pub fetch_link_titles: bool,
+    pub capture_retention: CaptureRetention,
+    pub archive: ArchivePolicy,
+}
+
+pub type SettingsState = Mutex<AppSettings>;
+
+/// The settings as the frontend gets them. Launch at login is read from the
+/// OS autostart entry every time instead of being stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsView {
+    #[serde(flatten)]
+    pub settings: AppSettings,
+    pub launch_at_login: bool,
+}
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve config directory: {}", e))?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+/// Reads the settings file, falling back to defaults if it is missing or invalid.
+pub fn load(app: &AppHandle) -> AppSettings {
+    settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let raw = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, raw).map_err(|e| format!("Failed to write settings: {}", e))
+}
+
+/// Returns a copy of the current settings.
+pub fn current(app: &AppHandle) -> AppSettings {
+    app.state::<SettingsState>()
+        .lock()
+        .map(|settings| settings.clone())
+        .unwrap_or_default()
+}
+
+/// Applies `change` to the in-memory settings and persists the result.
+pub fn update<F>(app: &AppHandle, change: F) -> Result<AppSettings, String>
+where
+    F: FnOnce(&mut AppSettings),
+{
+    let state = app.state::<SettingsState>();
+    let mut settings = state
+        .lock()
+        .map_err(|e| format!("Settings lock poisoned: {}", e))?;
+    change(&mut settings);
+    save(app, &settings)?;
+    Ok(settings.clone())
+}
+
+/// Effective UI language, taking the settings override into account.
+pub fn locale(app: &AppHandle) -> Locale {
+    i18n::resolve(current(app).language)
+}
+
+/// The configured placement of window `label`, see
+/// `WindowPlacement::default_for` for defaults.
+pub fn window_placement(app: &AppHandle, label: &str) -> WindowPlacement {
+    current(app)
+        .window_placement
+        .get(label)
+        .copied()
+        .unwrap_or_else(|| WindowPlacement::default_for(label))
+}
+
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> SettingsView {
+    let launch_at_login = autostart::is_registered(&app).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        false
+    });
+    SettingsView {
+        settings: current(&app),
+        launch_at_login,
+    }
+}
+
+#[tauri::command]
+pub fn get_locale(app: AppHandle) -> Locale {
+    locale(&app)
+}
+
+#[tauri::command]
+pub async fn set_language(app: AppHandle, language: Option<Locale>) -> Result<Locale, String> {
+    update(&app, |s| s.language = language)?;
+
+    windows::apply_window_titles(&app);
+    tray::refresh(&app).await?;
+
+    Ok(locale(&app))
+}
+
+#[tauri::command]
+pub fn get_work_calendar(app: AppHandle) -> WorkCalendar {
+    current(&app).work_calendar
+}
+
+#[tauri::command]
+pub fn set_work_calendar(app: AppHandle, calendar: WorkCalendar) -> Result<(), String> {
+    update(&app, |s| s.work_calendar = calendar)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_sprint(app: AppHandle) -> SprintConfig {
+    current(&app).sprint
+}
+
+#[tauri::command]
+pub fn set_sprint(app: AppHandle, sprint: SprintConfig) -> Result<(), String> {
+    if !(1..=8).contains(&sprint.length_weeks) {
+        return Err(format!("Invalid sprint length: {}", sprint.length_weeks));
+    }
+    update(&app, |s| s.sprint = sprint)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_day_start_hour(app: AppHandle, hour: u32) -> Result<(), String> {
+    if hour > 23 {
+        return Err(format!("Invalid hour: {}", hour));
+    }
+    update(&app, |s| s.day_start_hour = hour)?;
+    days::set_day_start_hour(hour);
+    rollover::check(&app).await
+}
+
+#[tauri::command]
+pub fn set_inbox_auto_file_hours(app: AppHandle, hours: Option<u32>) -> Result<(), String> {
+    if hours.is_some_and(|h| !(1..=720).contains(&h)) {
+        return Err(format!("Invalid auto-file delay: {:?}", hours));
+    }
+    update(&app, |s| s.inbox_auto_file_hours = hours)?;
+    Ok(())
+}
+
+// Expired captures are archived or deleted on the next scheduler tick
+#[tauri::command]
+pub fn set_capture_retention(app: AppHandle, retention: CaptureRetention) -> Result<(), String> {
+    retention.validate()?;
+    update(&app, |s| s.capture_retention = retention)?;
+    Ok(())
+}
+
+// Old notes are archived on the next scheduler tick
+#[tauri::command]
+pub fn set_archive_policy(app: AppHandle, policy: ArchivePolicy) -> Result<(), String> {
+    policy.validate()?;
+    update(&app, |s| s.archive = policy)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_clipboard_capture_silent(app: AppHandle, silent: bool) -> Result<(), String> {
+    update(&app, |s| s.clipboard_capture_silent = silent)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_fetch_link_titles(app: AppHandle, fetch: bool) -> Result<(), String> {
+    update(&app, |s| s.fetch_link_titles = fetch)?;
+    Ok(())
+}
@@ -0,0 +1,282 @@
+// Writing statistics over the whole history. Notes are counted per local day
+// in SQL (`notes::day_totals`), so only one row per day reaches Rust, and the
+// result is cached until notes change.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::calendar::WorkCalendar;
+use crate::days;
+use crate::notes::{self, DayTotals};
+use crate::report::{DayCount, TagCount};
+
+const DAYS_WINDOW: i64 = 30;
+const WEEKS_WINDOW: i64 = 12;
+const TOP_TAGS: i64 = 10;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekCount {
+    /// Monday of the week
+    pub week_start: NaiveDate,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Stats {
+    pub total_notes: usize,
+    pub quick_capture_notes: usize,
+    pub regular_notes: usize,
+    /// Share of quick captures, 0 without notes
+    pub quick_capture_ratio: f64,
+    /// The last 30 days up to today, including days without notes
+    pub per_day: Vec<DayCount>,
+    /// The last 12 weeks up to the current one
+    pub per_week: Vec<WeekCount>,
+    pub top_tags: Vec<TagCount>,
+    /// Over all workdays since the first note
+    pub average_per_workday: f64,
+    /// Workdays in a row with at least one note. An empty today does not
+    /// break the streak until the day is over.
+    pub current_streak: u32,
+}
+
+/// Everything a cached result depends on besides the notes themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheKey {
+    pub today: NaiveDate,
+    pub day_start_hour: u32,
+    pub calendar: WorkCalendar,
+}
+
+/// Cleared whenever notes change.
+pub type StatsCache = Mutex<Option<(CacheKey, Stats)>>;
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(i64::from(day.weekday().num_days_from_monday()))
+}
+
+fn current_streak(
+    counts: &BTreeMap<NaiveDate, usize>,
+    today: NaiveDate,
+    calendar: &WorkCalendar,
+) -> u32 {
+    let has_notes = |day: NaiveDate| counts.get(&day).is_some_and(|n| *n > 0);
+    let mut day = if calendar.is_workday(today) && has_notes(today) {
+        Some(today)
+    } else {
+        calendar.previous_workday(today)
+    };
+    let mut streak = 0;
+    while let Some(current) = day {
+        if !has_notes(current) {
+            break;
+        }
+        streak += 1;
+        day = calendar.previous_workday(current);
+    }
+    streak
+}
+
+/// Counts for every day from `from` through `to`, zero for days without
+/// notes, e.g. for a calendar heatmap. `totals` is sorted by day.
+pub fn fill_days(from: NaiveDate, to: NaiveDate, totals: &[DayTotals]) -> Vec<DayCount> {
+    days::each_day(from, to)
+        .map(|day| DayCount {
+            day,
+            count: totals
+                .binary_search_by_key(&day, |t| t.day)
+                .map_or(0, |i| totals[i].notes),
+        })
+        .collect()
+}
+
+/// Aggregates per-day totals, `tags` is already sorted by use.
+pub fn compute(
+    days: &[DayTotals],
+    tags: Vec<TagCount>,
+    today: NaiveDate,
+    calendar: &WorkCalendar,
+) -> Stats {
+    let counts: BTreeMap<NaiveDate, usize> = days.iter().map(|d| (d.day, d.notes)).collect();
+    let total_notes: usize = days.iter().map(|d| d.notes).sum();
+    let quick_capture_notes: usize = days.iter().map(|d| d.quick_captures).sum();
+
+    let per_day = fill_days(today - Duration::days(DAYS_WINDOW - 1), today, days);
+
+    let first_week = week_start(today) - Duration::weeks(WEEKS_WINDOW - 1);
+    let mut weeks: BTreeMap<NaiveDate, usize> = (0..WEEKS_WINDOW)
+        .map(|n| (first_week + Duration::weeks(n), 0))
+        .collect();
+    for (day, notes) in counts.range(first_week..=today) {
+        *weeks.entry(week_start(*day)).or_default() += notes;
+    }
+
+    let average_per_workday = match counts.keys().next() {
+        Some(first) => {
+            let workdays = days::each_day(*first, today)
+                .filter(|day| calendar.is_workday(*day))
+                .count();
+            let on_workdays: usize = counts
+                .iter()
+                .filter(|(day, _)| calendar.is_workday(**day))
+                .map(|(_, notes)| notes)
+                .sum();
+            if workdays > 0 {
+                on_workdays as f64 / workdays as f64
+            } else {
+                0.0
+            }
+        }
+        None => 0.0,
+    };
+
+    Stats {
+        total_notes,
+        quick_capture_notes,
+        regular_notes: total_notes - quick_capture_notes,
+        quick_capture_ratio: if total_notes > 0 {
+            quick_capture_notes as f64 / total_notes as f64
+        } else {
+            0.0
+        },
+        per_day,
+        per_week: weeks
+            .into_iter()
+            .map(|(week_start, count)| WeekCount { week_start, count })
+            .collect(),
+        top_tags: tags,
+        average_per_workday,
+        current_streak: current_streak(&counts, today, calendar),
+    }
+}
+
+pub async fn load(
+    pool: &SqlitePool,
+    today: NaiveDate,
+    calendar: &WorkCalendar,
+) -> Result<Stats, String> {
+    let day_totals = notes::day_totals(pool, None).await?;
+
+    let tags: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT tag, COUNT(*) AS uses FROM note_tags
+         GROUP BY tag ORDER BY uses DESC, tag LIMIT ?1",
+    )
+    .bind(TOP_TAGS)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to count tags: {}", e))?;
+    let tags = tags
+        .into_iter()
+        .map(|(tag, uses)| TagCount {
+            tag,
+            count: usize::try_from(uses).unwrap_or(0),
+        })
+        .collect();
+
+    Ok(compute(&day_totals, tags, today, calendar))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn totals(d: u32, notes: usize, quick_captures: usize) -> DayTotals {
+        DayTotals {
+            day: day(d),
+            notes,
+            quick_captures,
+        }
+    }
+
+    #[test]
+    fn counts_ratio_and_windows() {
+        let days = [totals(5, 2, 1), totals(12, 3, 0), totals(19, 1, 1)];
+        let stats = compute(&days, Vec::new(), day(19), &WorkCalendar::default());
+        assert_eq!(stats.total_notes, 6);
+        assert_eq!(stats.quick_capture_notes, 2);
+        assert_eq!(stats.regular_notes, 4);
+        assert!((stats.quick_capture_ratio - 1.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(stats.per_day.len(), 30);
+        assert_eq!(
+            stats.per_day.last().map(|d| (d.day, d.count)),
+            Some((day(19), 1))
+        );
+        assert_eq!(stats.per_week.len(), 12);
+        let recent: Vec<usize> = stats
+            .per_week
+            .iter()
+            .rev()
+            .take(3)
+            .map(|w| w.count)
+            .collect();
+        assert_eq!(recent, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn average_counts_workdays_since_the_first_note() {
+        // Mon 12th to Mon 19th are six workdays, the weekend note is ignored
+        let days = [totals(12, 4, 0), totals(17, 5, 0), totals(19, 2, 0)];
+        let stats = compute(&days, Vec::new(), day(19), &WorkCalendar::default());
+        assert!((stats.average_per_workday - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn streak_skips_weekends_and_tolerates_an_empty_today() {
+        let calendar = WorkCalendar::default();
+        // Wed 14th to Fri 16th, nothing yet on Monday the 19th
+        let days = [
+            totals(13, 0, 0),
+            totals(14, 1, 0),
+            totals(15, 2, 0),
+            totals(16, 1, 0),
+        ];
+        assert_eq!(
+            compute(&days, Vec::new(), day(19), &calendar).current_streak,
+            3
+        );
+
+        let with_today = [
+            totals(14, 1, 0),
+            totals(15, 2, 0),
+            totals(16, 1, 0),
+            totals(19, 1, 0),
+        ];
+        assert_eq!(
+            compute(&with_today, Vec::new(), day(19), &calendar).current_streak,
+            4
+        );
+
+        let gap = [totals(15, 1, 0), totals(19, 1, 0)];
+        assert_eq!(
+            compute(&gap, Vec::new(), day(19), &calendar).current_streak,
+            1
+        );
+    }
+
+    #[test]
+    fn heatmap_fills_days_without_notes() {
+        let days = [totals(2, 3, 0), totals(4, 1, 1)];
+        let counts: Vec<usize> = fill_days(day(1), day(5), &days)
+            .iter()
+            .map(|d| d.count)
+            .collect();
+        assert_eq!(counts, vec![0, 3, 0, 1, 0]);
+    }
+
+    #[test]
+    fn empty_history() {
+        let stats = compute(&[], Vec::new(), day(19), &WorkCalendar::default());
+        assert_eq!(stats.total_notes, 0);
+        assert_eq!(stats.quick_capture_ratio, 0.0);
+        assert_eq!(stats.average_per_workday, 0.0);
+        assert_eq!(stats.current_streak, 0);
+    }
+}
@@ -0,0 +1,198 @@
+// Notes that are tasks. `task_state` is NULL for plain notes; open tasks
+// from earlier days are carried over into today until they are closed.
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::days;
+use crate::notes::{self, NoteEntry, NOTE_COLUMNS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskState {
+    Open,
+    Done,
+    Cancelled,
+}
+
+impl TaskState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskState::Open => "open",
+            TaskState::Done => "done",
+            TaskState::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<TaskState> {
+        match value {
+            "open" => Some(TaskState::Open),
+            "done" => Some(TaskState::Done),
+            "cancelled" => Some(TaskState::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn is_closed(self) -> bool {
+        self != TaskState::Open
+    }
+
+    /// Markdown checkbox, `[-]` is the common extension for cancelled items.
+    pub fn checkbox(self) -> &'static str {
+        match self {
+            TaskState::Open => "[ ]",
+            TaskState::Done => "[x]",
+            TaskState::Cancelled => "[-]",
+        }
+    }
+
+    /// Open becomes done, anything closed is reopened.
+    pub fn toggled(state: Option<TaskState>) -> TaskState {
+        match state {
+            Some(TaskState::Open) => TaskState::Done,
+            None | Some(TaskState::Done) | Some(TaskState::Cancelled) => TaskState::Open,
+        }
+    }
+}
+
+/// Splits a leading `- [ ]`, `- [x]` or `- [-]` off imported content. The
+/// list marker is optional, `*` works as well.
+pub fn from_markdown(content: &str) -> (Option<TaskState>, &str) {
+    let trimmed = content.trim_start();
+    let rest = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .unwrap_or(trimmed);
+    let state = match rest.get(..3) {
+        Some("[ ]") => TaskState::Open,
+        Some("[x]") | Some("[X]") => TaskState::Done,
+        Some("[-]") | Some("[~]") => TaskState::Cancelled,
+        _ => return (None, content),
+    };
+    let text = &rest[3..];
+    // `[x]foo` is not a checkbox
+    if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+        return (None, content);
+    }
+    (Some(state), text.trim())
+}
+
+/// Content with its checkbox, as written on export.
+pub fn to_markdown(state: Option<TaskState>, content: &str) -> String {
+    match state {
+        Some(state) => format!("{} {}", state.checkbox(), content),
+        None => content.to_string(),
+    }
+}
+
+/// Sets or clears the task state, stamping `completed_at` when it closes.
+pub async fn set_state(
+    pool: &SqlitePool,
+    id: i64,
+    state: Option<TaskState>,
+) -> Result<NoteEntry, String> {
+    let now = notes::timestamp(&Utc::now());
+    let result = sqlx::query(
+        "UPDATE notes SET task_state = ?1, completed_at = ?2, updated_at = ?3 WHERE id = ?4",
+    )
+    .bind(state.map(TaskState::as_str))
+    .bind(state.filter(|s| s.is_closed()).map(|_| now.clone()))
+    .bind(&now)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to update task: {}", e))?;
+    if result.rows_affected() == 0 {
+        return Err("Note not found".to_string());
+    }
+    notes::get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())
+}
+
+pub async fn toggle(pool: &SqlitePool, id: i64) -> Result<NoteEntry, String> {
+    let note = notes::get_note(pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())?;
+    set_state(pool, id, Some(TaskState::toggled(note.task_state))).await
+}
+
+/// All open tasks, oldest first.
+pub async fn open_tasks(pool: &SqlitePool) -> Result<Vec<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes WHERE task_state = 'open' AND archived_at IS NULL
+         ORDER BY created_at ASC",
+        NOTE_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch open tasks: {}", e))
+}
+
+/// Open tasks created before the given local day.
+pub async fn carried_over(pool: &SqlitePool, day: NaiveDate) -> Result<Vec<NoteEntry>, String> {
+    sqlx::query_as::<_, NoteEntry>(&format!(
+        "SELECT {} FROM notes
+         WHERE task_state = 'open' AND archived_at IS NULL AND created_at < ?1
+         ORDER BY created_at ASC",
+        NOTE_COLUMNS
+    ))
+    .bind(notes::timestamp(&days::start_of_day(day)))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch carried over tasks: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_checkboxes_map_to_states() {
+        assert_eq!(
+            from_markdown("- [ ] write tests"),
+            (Some(TaskState::Open), "write tests")
+        );
+        assert_eq!(
+            from_markdown("- [x] deploy"),
+            (Some(TaskState::Done), "deploy")
+        );
+        assert_eq!(
+            from_markdown("* [X] deploy"),
+            (Some(TaskState::Done), "deploy")
+        );
+        assert_eq!(
+            from_markdown("[-] dropped"),
+            (Some(TaskState::Cancelled), "dropped")
+        );
+    }
+
+    #[test]
+    fn text_without_checkbox_is_untouched() {
+        assert_eq!(from_markdown("- plain item"), (None, "- plain item"));
+        assert_eq!(from_markdown("[x]ray results"), (None, "[x]ray results"));
+        assert_eq!(from_markdown("[ ]"), (Some(TaskState::Open), ""));
+        assert_eq!(from_markdown("über [ ]"), (None, "über [ ]"));
+    }
+
+    #[test]
+    fn markdown_round_trips() {
+        for state in [TaskState::Open, TaskState::Done, TaskState::Cancelled] {
+            let exported = to_markdown(Some(state), "review PR");
+            assert_eq!(from_markdown(&exported), (Some(state), "review PR"));
+        }
+        assert_eq!(to_markdown(None, "note"), "note");
+    }
+
+    #[test]
+    fn toggling_opens_and_closes() {
+        assert_eq!(TaskState::toggled(None), TaskState::Open);
+        assert_eq!(TaskState::toggled(Some(TaskState::Open)), TaskState::Done);
+        assert_eq!(TaskState::toggled(Some(TaskState::Done)), TaskState::Open);
+        assert_eq!(
+            TaskState::toggled(Some(TaskState::Cancelled)),
+            TaskState::Open
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use chrono::NaiveDate;
+use tauri::{
+    menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    AppHandle, Listener, Manager,
+};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::i18n::{self, Locale, Msg};
+use crate::{attachments, days, db, events, notes, report, settings, tasks, windows};
+
+pub const TRAY_ID: &str = "main";
+
+const RECENT_NOTES: usize = 5;
+const RECENT_DAYS: usize = 7;
+const RECENT_DAYS_LOOKBACK: u64 = 30;
+const LABEL_MAX_CHARS: usize = 40;
+
+// Data the dynamic part of the menu is built from
+#[derive(Default)]
+struct TraySnapshot {
+    today: Vec<notes::NoteEntry>,
+    recent_days: Vec<NaiveDate>,
+}
+
+fn menu_label(content: &str) -> String {
+    let first_line = content.trim().lines().next().unwrap_or_default();
+    if first_line.chars().count() > LABEL_MAX_CHARS {
+        let truncated: String = first_line.chars().take(LABEL_MAX_CHARS - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        first_line.to_string()
+    }
+}
+
+fn build_menu(
+    app: &AppHandle,
+    locale: Locale,
+    snapshot: &TraySnapshot,
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let mut builder = MenuBuilder::new(app);
+
+    if snapshot.today.is_empty() {
+        let empty = MenuItemBuilder::with_id("no_notes", i18n::t(locale, Msg::TrayNoNotesToday))
+            .enabled(false)
+            .build(app)?;
+        builder = builder.item(&empty);
+    } else {
+        // Newest first, clicking copies the note to the clipboard
+        for note in snapshot.today.iter().rev().take(RECENT_NOTES) {
+            let label = tasks::to_markdown(note.task_state, &menu_label(&note.content));
+            builder = builder.text(format!("note:{}", note.id), label);
+        }
+    }
+
+    let mut days_menu = SubmenuBuilder::new(app, i18n::t(locale, Msg::TrayRecentDays));
+    if snapshot.recent_days.is_empty() {
+        days_menu = days_menu.item(
+            &MenuItemBuilder::with_id("no_days", i18n::t(locale, Msg::TrayNoRecentDays))
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    for day in &snapshot.recent_days {
+        days_menu = days_menu.text(
+            format!("day:{}", day.format("%Y-%m-%d")),
+            i18n::format_day_label(locale, *day),
+        );
+    }
+
+    builder
+        .separator()
+        .text("copy_standup", i18n::t(locale, Msg::TrayCopyStandup))
+        .text(
+            "copy_sprint_summary",
+            i18n::t(locale, Msg::TrayCopySprintSummary),
+        )
+        .text("quick_capture", i18n::t(locale, Msg::TrayQuickCapture))
+        .item(&days_menu.build()?)
+        .separator()
+        .text("show_notes", i18n::t(locale, Msg::TrayShowNotes))
+        .text("settings", i18n::t(locale, Msg::TraySettings))
+        .text("quit", i18n::t(locale, Msg::TrayQuit))
+        .build()
+}
+
+async fn load_snapshot(app: &AppHandle) -> Result<TraySnapshot, String> {
+    let pool = db::pool(app).await?;
+    let today = days::today();
+    Ok(TraySnapshot {
+        today: notes::day_view(&pool, today).await?.into_entries(),
+        recent_days: notes::recent_days(&pool, today, RECENT_DAYS_LOOKBACK, RECENT_DAYS).await?,
+    })
+}
+
+/// Rebuilds the menu and updates the note count shown on the tray icon.
+pub async fn refresh(app: &AppHandle) -> Result<(), String> {
+    let snapshot = load_snapshot(app).await?;
+    let tray = app
+        .tray_by_id(TRAY_ID)
+        .ok_or_else(|| "Tray icon not found".to_string())?;
+
+    let locale = settings::locale(app);
+    let menu = build_menu(app, locale, &snapshot).map_err(|e| e.to_string())?;
+    tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+
+    let count = snapshot.today.len();
+    let tooltip = i18n::t_with(locale, Msg::TrayTooltip, &[("count", &count.to_string())]);
+    tray.set_tooltip(Some(tooltip)).map_err(|e| e.to_string())?;
+    // Only shown next to the icon on macOS and Linux
+    let title = if count > 0 {
+        Some(count.to_string())
+    } else {
+        None
+    };
+    tray.set_title(title).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn spawn_refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = refresh(&app).await {
+            eprintln!("Failed to refresh tray menu: {}", e);
+        }
+    });
+}
+
+async fn copy_note(app: &AppHandle, id: i64) -> Result<(), String> {
+    let pool = db::pool(app).await?;
+    let note = notes::get_note(&pool, id)
+        .await?
+        .ok_or_else(|| "Note not found".to_string())?;
+    app.clipboard()
+        .write_text(note.content)
+        .map_err(|e| format!("Failed to copy note: {}", e))
+}
+
+async fn copy_standup(app: &AppHandle, day: NaiveDate) -> Result<(), String> {
+    let pool = db::pool(app).await?;
+    // Today's standup lists pinned notes and open tasks from earlier days
+    // as well, like the main window
+    let day_notes = notes::day_view(&pool, day).await?.into_entries();
+    let ids: Vec<i64> = day_notes.iter().map(|n| n.id).collect();
+    let attached = attachments::for_notes(&pool, &ids).await?;
+    app.clipboard()
+        .write_text(report::standup(
+            settings::locale(app),
+            day,
+            &day_notes,
+            &attached,
+        ))
+        .map_err(|e| format!("Failed to copy standup: {}", e))
+}
+
+async fn copy_sprint_summary(app: &AppHandle) -> Result<(), String> {
+    let pool = db::pool(app).await?;
+    let (from, to) = settings::current(app).sprint.sprint_of(days::today());
+    let summary = report::summarize(from, to, &notes::notes_in_range(&pool, from, to).await?);
+    app.clipboard()
+        .write_text(report::render_summary(settings::locale(app), &summary))
+        .map_err(|e| format!("Failed to copy sprint summary: {}", e))
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "show_notes" => windows::show_main_window(app),
+        "settings" => windows::show_settings_window(app),
+        "quick_capture" => windows::show_quick_capture(app),
+        "quit" => app.exit(0),
+        "copy_standup" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = copy_standup(&app, days::today()).await {
+                    eprintln!("Failed to copy standup: {}", e);
+                }
+            });
+        }
+        "copy_sprint_summary" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = copy_sprint_summary(&app).await {
+                    eprintln!("Failed to copy sprint summary: {}", e);
+                }
+            });
+        }
+        _ => {
+            if let Some(note_id) = id.strip_prefix("note:").and_then(|v| v.parse().ok()) {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = copy_note(&app, note_id).await {
+                        eprintln!("Failed to copy note {}: {}", note_id, e);
+                    }
+                });
+            } else if let Some(day) = id
+                .strip_prefix("day:")
+                .and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+            {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = copy_standup(&app, day).await {
+                        eprintln!("Failed to copy standup of {}: {}", day, e);
+                    }
+                });
+            }
+        }
+    }
+}
+
+pub fn create(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_menu(app, settings::locale(app), &TraySnapshot::default())?;
+
+    // Tray icon creation with proper positioner integration according to Context7
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .icon(app.default_window_icon().unwrap().clone())
+        .on_tray_icon_event(|tray, event| {
+            // CRITICAL: Handle tray events for positioner plugin FIRST
+            tauri_plugin_positioner::on_tray_event(tray.app_handle(), &event);
+
+            // Handle click events
+            if let TrayIconEvent::Click {
+                button,
+                button_state,
+                ..
+            } = event
+            {
+                if button == MouseButton::Left && button_state == MouseButtonState::Up {
+                    windows::toggle_main_window(tray.app_handle());
+                }
+            }
+        })
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()))
+        .build(app)?;
+
+    // Keep the menu in sync with the database
+    let handle = app.clone();
+    app.listen_any(events::NOTES_UPDATED, move |_| spawn_refresh(&handle));
+    spawn_refresh(app);
+
+    Ok(())
+}
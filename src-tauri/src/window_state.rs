@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{
+    AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window,
+    WindowEvent,
+};
+
+use crate::placement::{self, Anchor, Rect};
+use crate::{settings, windows};
+
+const STATE_FILE: &str = "window-state.json";
+
+/// How a window is placed when it is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowPlacement {
+    SnapToTray,
+    RememberPosition,
+    /// Centered on the monitor under the cursor
+    Center,
+    /// Spotlight style, on the monitor under the cursor
+    TopThird,
+}
+
+impl WindowPlacement {
+    /// Unless configured otherwise the main window snaps to the tray, quick
+    /// capture opens centered on the active monitor and settings reopens
+    /// where the user left it.
+    pub fn default_for(label: &str) -> WindowPlacement {
+        match label {
+            "main" => WindowPlacement::SnapToTray,
+            "quick-capture" => WindowPlacement::Center,
+            _ => WindowPlacement::RememberPosition,
+        }
+    }
+
+    /// Whether saved geometry is restored before falling back to the anchor.
+    pub fn restores_geometry(self) -> bool {
+        self == WindowPlacement::RememberPosition
+    }
+
+    /// Anchor used when the window is not restored from saved geometry.
+    pub fn anchor(self) -> Anchor {
+        match self {
+            WindowPlacement::SnapToTray => Anchor::NearTray,
+            WindowPlacement::TopThird => Anchor::TopThird,
+            WindowPlacement::RememberPosition | WindowPlacement::Center => Anchor::Center,
+        }
+    }
+}
+
+/// Last known outer position and inner size in physical pixels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub monitor: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Geometries {
+    windows: HashMap<String, WindowGeometry>,
+    /// Changed since the state file was last written
+    dirty: bool,
+}
+
+impl Geometries {
+    /// Records the geometry of window `label`, returns whether it changed.
+    fn update(&mut self, label: &str, geometry: WindowGeometry) -> bool {
+        if self.windows.get(label) == Some(&geometry) {
+            return false;
+        }
+        self.windows.insert(label.to_string(), geometry);
+        self.dirty = true;
+        true
+    }
+}
+
+#[derive(Default)]
+pub struct WindowStateStore(Mutex<Geometries>);
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve config directory: {}", e))?;
+    Ok(dir.join(STATE_FILE))
+}
+
+pub fn load(app: &AppHandle) -> WindowStateStore {
+    let windows = state_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    WindowStateStore(Mutex::new(Geometries {
+        windows,
+        dirty: false,
+    }))
+}
+
+/// Writes the state file, unless nothing changed since the last write.
+fn persist(app: &AppHandle) -> Result<(), String> {
+    let store = app.state::<WindowStateStore>();
+    let mut geometries = store
+        .0
+        .lock()
+        .map_err(|e| format!("Window state lock poisoned: {}", e))?;
+    if !geometries.dirty {
+        return Ok(());
+    }
+    let raw = serde_json::to_string_pretty(&geometries.windows)
+        .map_err(|e| format!("Failed to serialize window state: {}", e))?;
+    let path = state_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    fs::write(&path, raw).map_err(|e| format!("Failed to write window state: {}", e))?;
+    geometries.dirty = false;
+    Ok(())
+}
+
+fn current_geometry<R: Runtime>(window: &Window<R>) -> Option<WindowGeometry> {
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    // Minimized windows report a zero size on Windows
+    if size.width == 0 || size.height == 0 {
+        return None;
+    }
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        monitor,
+    })
+}
+
+/// Records moves and resizes in memory and writes them out once the window
+/// loses focus or is closed, instead of on every drag step.
+pub fn track(window: &Window<tauri::Wry>, event: &WindowEvent) {
+    let app = window.app_handle();
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            if let Some(geometry) = current_geometry(window) {
+                if let Ok(mut geometries) = app.state::<WindowStateStore>().0.lock() {
+                    geometries.update(window.label(), geometry);
+                }
+            }
+        }
+        WindowEvent::Focused(false) | WindowEvent::CloseRequested { .. } => {
+            if let Err(e) = persist(app) {
+                eprintln!("Failed to save window state: {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl WindowGeometry {
+    fn rect(&self) -> Rect {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+}
+
+/// Index of the saved monitor if it is still connected, otherwise of the one
+/// the window overlaps most. `monitors` are names and bounds.
+fn saved_monitor(monitors: &[(Option<&str>, Rect)], geometry: &WindowGeometry) -> Option<usize> {
+    if let Some(name) = geometry.monitor.as_deref() {
+        if let Some(index) = monitors.iter().position(|(n, _)| *n == Some(name)) {
+            return Some(index);
+        }
+    }
+    let overlap = |bounds: &Rect| bounds.overlap(&geometry.rect());
+    monitors
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, (_, bounds))| overlap(bounds))
+        .filter(|(_, (_, bounds))| overlap(bounds) > 0)
+        .map(|(index, _)| index)
+}
+
+/// The saved monitor, see `saved_monitor`, otherwise the primary monitor.
+fn target_monitor(window: &WebviewWindow, geometry: &WindowGeometry) -> Option<Monitor> {
+    let monitors = window.available_monitors().ok()?;
+    let screens: Vec<(Option<&str>, Rect)> = monitors
+        .iter()
+        .map(|m| (m.name().map(String::as_str), windows::screen_of(m).bounds))
+        .collect();
+    match saved_monitor(&screens, geometry) {
+        Some(index) => monitors.get(index).cloned(),
+        None => window.primary_monitor().ok().flatten(),
+    }
+}
+
+/// Where saved geometry goes, kept inside `work_area` so the window stays
+/// reachable if its monitor was unplugged or shrunk.
+fn restored_rect(saved: &WindowGeometry, work_area: Option<Rect>) -> Rect {
+    match work_area {
+        Some(area) => placement::clamp(saved.rect(), area),
+        None => saved.rect(),
+    }
+}
+
+/// Applies the saved geometry, returns `false` if there is nothing to restore.
+pub fn restore(window: &WebviewWindow) -> bool {
+    let saved = match window.app_handle().state::<WindowStateStore>().0.lock() {
+        Ok(geometries) => geometries.windows.get(window.label()).cloned(),
+        Err(_) => None,
+    };
+    let saved = match saved {
+        Some(saved) => saved,
+        None => return false,
+    };
+
+    let work_area = target_monitor(window, &saved).map(|m| windows::screen_of(&m).work_area);
+    let rect = restored_rect(&saved, work_area);
+
+    if window.is_resizable().unwrap_or(false) {
+        let _ = window.set_size(PhysicalSize::new(rect.width, rect.height));
+    }
+    window
+        .set_position(PhysicalPosition::new(rect.x, rect.y))
+        .is_ok()
+}
+
+#[tauri::command]
+pub fn get_window_placement(app: AppHandle, label: String) -> WindowPlacement {
+    settings::window_placement(&app, &label)
+}
+
+#[tauri::command]
+pub fn set_window_placement(
+    app: AppHandle,
+    label: String,
+    placement: WindowPlacement,
+) -> Result<(), String> {
+    settings::update(&app, |s| {
+        s.window_placement.insert(label, placement);
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geometry(x: i32, y: i32, monitor: Option<&str>) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width: 400,
+            height: 300,
+            monitor: monitor.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn only_remembered_windows_restore_geometry() {
+        assert_eq!(
+            WindowPlacement::default_for("main"),
+            WindowPlacement::SnapToTray
+        );
+        assert_eq!(
+            WindowPlacement::default_for("quick-capture"),
+            WindowPlacement::Center
+        );
+        assert_eq!(
+            WindowPlacement::default_for("settings"),
+            WindowPlacement::RememberPosition
+        );
+        assert!(WindowPlacement::RememberPosition.restores_geometry());
+        assert!(!WindowPlacement::SnapToTray.restores_geometry());
+        assert_eq!(WindowPlacement::SnapToTray.anchor(), Anchor::NearTray);
+        assert_eq!(WindowPlacement::TopThird.anchor(), Anchor::TopThird);
+    }
+
+    #[test]
+    fn unchanged_geometry_does_not_mark_the_state_dirty() {
+        let mut geometries = Geometries::default();
+        assert!(geometries.update("main", geometry(10, 20, None)));
+        assert!(geometries.dirty);
+
+        geometries.dirty = false;
+        assert!(!geometries.update("main", geometry(10, 20, None)));
+        assert!(!geometries.dirty);
+        assert!(geometries.update("main", geometry(11, 20, None)));
+        assert!(geometries.dirty);
+    }
+
+    #[test]
+    fn saved_monitor_is_preferred_then_the_most_overlapped() {
+        let left = Rect::new(0, 0, 1920, 1080);
+        let right = Rect::new(1920, 0, 2560, 1440);
+        let monitors = [(Some("Built-in"), left), (Some("DELL"), right)];
+
+        assert_eq!(
+            saved_monitor(&monitors, &geometry(100, 100, Some("DELL"))),
+            Some(1)
+        );
+        // Unplugged monitor, the window still sits on the right one
+        assert_eq!(
+            saved_monitor(&monitors, &geometry(2000, 100, Some("LG"))),
+            Some(1)
+        );
+        assert_eq!(saved_monitor(&monitors, &geometry(-5000, 0, None)), None);
+    }
+
+    #[test]
+    fn restored_windows_are_kept_on_screen() {
+        let saved = geometry(3000, 100, None);
+        let area = Rect::new(0, 25, 1920, 1055);
+        let rect = restored_rect(&saved, Some(area));
+        assert_eq!((rect.x, rect.y), (1520, 100));
+        assert_eq!(restored_rect(&saved, None), saved.rect());
+    }
+}
@@ -0,0 +1,208 @@
+use tauri::{
+    AppHandle, LogicalPosition, Manager, Monitor, PhysicalPosition, Position, WebviewWindow,
+};
+
+use crate::i18n::{self, Msg};
+use crate::placement::{self, Anchor, Rect, Screen};
+use crate::window_state;
+use crate::{settings, tray};
+
+const WINDOW_TITLES: [(&str, Msg); 3] = [
+    ("main", Msg::MainWindowTitle),
+    ("quick-capture", Msg::QuickCaptureWindowTitle),
+    ("settings", Msg::SettingsWindowTitle),
+];
+
+/// Re-applies localized titles to all open windows.
+pub fn apply_window_titles(app: &AppHandle) {
+    let locale = settings::locale(app);
+    for (label, msg) in WINDOW_TITLES {
+        if let Some(window) = app.get_webview_window(label) {
+            let _ = window.set_title(i18n::t(locale, msg));
+        }
+    }
+}
+
+pub fn screen_of(monitor: &Monitor) -> Screen {
+    let work_area = monitor.work_area();
+    Screen {
+        bounds: Rect::new(
+            monitor.position().x,
+            monitor.position().y,
+            monitor.size().width,
+            monitor.size().height,
+        ),
+        work_area: Rect::new(
+            work_area.position.x,
+            work_area.position.y,
+            work_area.size.width,
+            work_area.size.height,
+        ),
+    }
+}
+
+/// The monitor under the mouse cursor, which is where the user is looking.
+fn active_screen(app: &AppHandle, window: &WebviewWindow) -> Option<Screen> {
+    let screens: Vec<Screen> = window
+        .available_monitors()
+        .ok()?
+        .iter()
+        .map(screen_of)
+        .collect();
+    let bounds: Vec<Rect> = screens.iter().map(|s| s.bounds).collect();
+
+    app.cursor_position()
+        .ok()
+        .and_then(|cursor| placement::monitor_at(&bounds, cursor.x, cursor.y))
+        .map(|index| screens[index])
+        .or_else(|| {
+            window
+                .primary_monitor()
+                .ok()
+                .flatten()
+                .map(|m| screen_of(&m))
+        })
+}
+
+/// Scale factor of the monitor showing the logical point, or of the primary
+/// monitor if none does.
+fn scale_factor_at(app: &AppHandle, point: LogicalPosition<f64>) -> f64 {
+    let monitors = app.available_monitors().unwrap_or_default();
+    monitors
+        .iter()
+        .find(|m| {
+            let scale = m.scale_factor();
+            screen_of(m)
+                .bounds
+                .contains(point.x * scale, point.y * scale)
+        })
+        .map(|m| m.scale_factor())
+        .or_else(|| {
+            app.primary_monitor()
+                .ok()
+                .flatten()
+                .map(|m| m.scale_factor())
+        })
+        .unwrap_or(1.0)
+}
+
+fn tray_rect(app: &AppHandle) -> Option<Rect> {
+    let rect = app.tray_by_id(tray::TRAY_ID)?.rect().ok()??;
+    // Some platforms report the tray in logical units, which only map to
+    // pixels with the scale of the monitor the icon sits on
+    let scale = match rect.position {
+        Position::Physical(_) => 1.0,
+        Position::Logical(point) => scale_factor_at(app, point),
+    };
+    let position = rect.position.to_physical::<i32>(scale);
+    let size = rect.size.to_physical::<u32>(scale);
+    Some(Rect::new(position.x, position.y, size.width, size.height))
+}
+
+fn move_to_anchor(app: &AppHandle, window: &WebviewWindow, anchor: Anchor) {
+    let (Some(screen), Ok(size)) = (active_screen(app, window), window.outer_size()) else {
+        return;
+    };
+    let (x, y) =
+        placement::anchored_position(&screen, (size.width, size.height), anchor, tray_rect(app));
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+}
+
+/// Positions a window according to its placement setting. Remembered windows
+/// without saved geometry yet open centered on the active monitor.
+fn place(app: &AppHandle, window: &WebviewWindow) {
+    let placement = settings::window_placement(app, window.label());
+    if placement.restores_geometry() && window_state::restore(window) {
+        return;
+    }
+    move_to_anchor(app, window, placement.anchor());
+}
+
+fn show_and_focus(window: &WebviewWindow) {
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        place(app, &window);
+        show_and_focus(&window);
+    }
+}
+
+pub fn toggle_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+            return;
+        }
+    }
+    show_main_window(app);
+}
+
+pub fn show_quick_capture(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("quick-capture") {
+        place(app, &window);
+        show_and_focus(&window);
+    } else if let Ok(window) = tauri::WebviewWindowBuilder::new(
+        app,
+        "quick-capture",
+        tauri::WebviewUrl::App("quick-capture.html".into()),
+    )
+    .title(i18n::t(settings::locale(app), Msg::QuickCaptureWindowTitle))
+    .inner_size(400.0, 300.0)
+    .center()
+    .resizable(false)
+    .minimizable(false)
+    .maximizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .build()
+    {
+        // Create the quick capture window if it doesn't exist
+        place(app, &window);
+        show_and_focus(&window);
+    }
+}
+
+pub fn toggle_quick_capture(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("quick-capture") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+            return;
+        }
+    }
+    show_quick_capture(app);
+}
+
+pub fn show_settings_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        place(app, &window);
+        show_and_focus(&window);
+    } else if let Ok(window) = tauri::WebviewWindowBuilder::new(
+        app,
+        "settings",
+        tauri::WebviewUrl::App("settings.html".into()),
+    )
+    .title(i18n::t(settings::locale(app), Msg::SettingsWindowTitle))
+    .inner_size(400.0, 500.0)
+    .resizable(false)
+    .maximizable(false)
+    .minimizable(false)
+    .center()
+    .build()
+    {
+        place(app, &window);
+        show_and_focus(&window);
+    }
+}
+
+#[tauri::command]
+pub fn open_settings_window(app: AppHandle) {
+    show_settings_window(&app);
+}
+
+#[tauri::command]
+pub fn open_quick_capture(app: AppHandle) {
+    show_quick_capture(&app);
+}
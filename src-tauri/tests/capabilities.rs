@@ -0,0 +1,49 @@
+//! The webviews must not talk to the database directly, every data
+//! operation goes through a typed command instead.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+fn permission_ids(capability: &Value) -> Vec<String> {
+    capability["permissions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|permission| match permission {
+            Value::String(id) => Some(id.clone()),
+            // Scoped permissions are objects with an identifier
+            Value::Object(scoped) => scoped["identifier"].as_str().map(str::to_string),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn no_capability_grants_sql_access() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("capabilities");
+    let mut checked = 0;
+    for entry in fs::read_dir(&dir).expect("capabilities directory") {
+        let path = entry.expect("capability file").path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let raw = fs::read_to_string(&path).expect("readable capability");
+        let capability: Value = serde_json::from_str(&raw).expect("valid capability JSON");
+        for id in permission_ids(&capability) {
+            assert!(
+                !id.starts_with("sql:"),
+                "{} grants {}, use a Rust command instead",
+                path.display(),
+                id
+            );
+        }
+        checked += 1;
+    }
+    assert!(
+        checked > 0,
+        "no capability files found in {}",
+        dir.display()
+    );
+}
@@ -1,7 +1,10 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { listen } from "@tauri-apps/api/event";
-import Database from "@tauri-apps/plugin-sql";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import DayCalendar from "./DayCalendar";
+import { useDateLocale } from "./useDateLocale";
 import "./App.css";
 
 interface NoteEntry {
@@ -10,17 +13,81 @@ interface NoteEntry {
   created_at: string;
   updated_at: string;
   is_quick_capture: boolean;
+  task_state: "open" | "done" | "cancelled" | null;
+  pin_order: number | null;
+  starred: boolean;
+  archived_at: string | null;
+}
+
+interface Attachment {
+  id: number;
+  note_id: number;
+  name: string;
+  size: number;
+}
+
+interface NotesChanged {
+  kind: "created" | "updated" | "deleted" | "bulk";
+  ids: number[];
+  days: string[];
+}
+
+interface NotePage {
+  pinned: NoteEntry[];
+  notes: NoteEntry[];
+  older: string | null;
+  newer: string | null;
+}
+
+// One day as the backend sees it, see notes::DayView
+interface DayView {
+  pinned: NoteEntry[];
+  carried_over: NoteEntry[];
+  notes: NoteEntry[];
+  archived: NoteEntry[];
+}
+
+// Notes fetched per lazy-load step of the main list
+const PAGE_SIZE = 50;
+
+interface Stats {
+  current_streak: number;
+  average_per_workday: number;
 }
 
 function App() {
   const [allNotes, setAllNotes] = useState<NoteEntry[]>([]);
+  const [openTasks, setOpenTasks] = useState<NoteEntry[]>([]);
+  const [inbox, setInbox] = useState<NoteEntry[]>([]);
+  const [pinned, setPinned] = useState<NoteEntry[]>([]);
+  const [stats, setStats] = useState<Stats | null>(null);
+  const [selectedDay, setSelectedDay] = useState<string | null>(null);
+  const [dayNotes, setDayNotes] = useState<NoteEntry[]>([]);
+  const [dayBacklinks, setDayBacklinks] = useState<NoteEntry[]>([]);
+  const [searchQuery, setSearchQuery] = useState("");
+  // Lists archived notes only, searched by the query if there is one
+  const [showArchive, setShowArchive] = useState(false);
+  const [searchResults, setSearchResults] = useState<NoteEntry[]>([]);
+  const [olderCursor, setOlderCursor] = useState<string | null>(null);
+  const [isLoadingMore, setIsLoadingMore] = useState(false);
+  // Reloads keep as many notes as were already scrolled into view
+  const loadedCount = useRef(0);
   const [isLoading, setIsLoading] = useState(true);
   const [editingId, setEditingId] = useState<number | null>(null);
   const [editingContent, setEditingContent] = useState("");
+  const [editingWhen, setEditingWhen] = useState("");
+  const [attachments, setAttachments] = useState<Attachment[]>([]);
+  // Read by the drag-and-drop listener, which is registered once
+  const editingIdRef = useRef<number | null>(null);
+  const [remindingId, setRemindingId] = useState<number | null>(null);
+  const [reminderWhen, setReminderWhen] = useState("");
   const [isVisible, setIsVisible] = useState(false);
+  // Bumped on day-changed so the header re-renders after midnight
+  const [currentDay, setCurrentDay] = useState(() => new Date());
+  const dateLocale = useDateLocale();
 
   // Get today's date in a readable format
-  const today = new Date().toLocaleDateString('de-DE', {
+  const today = currentDay.toLocaleDateString(dateLocale, {
     weekday: 'long',
     year: 'numeric',
     month: 'long',
@@ -30,19 +97,21 @@ function App() {
   useEffect(() => {
     loadAllNotes();
     
-    // Listen for note updates from database changes
-    const unlistenNoteUpdate = listen("note-updated", () => {
-      console.log("Main App: Received note-updated event");
+    // Every note change arrives as notes-updated, see events.rs
+    const unlistenNotesUpdate = listen<NotesChanged>("notes-updated", (event) => {
+      console.log("Main App: Received notes-updated event", event.payload.kind, event.payload.ids);
       loadAllNotes();
     });
 
-    const unlistenNotesUpdate = listen("notes-updated", () => {
-      console.log("Main App: Received notes-updated event");
+    const unlistenRefresh = listen("refresh-data", () => {
+      console.log("Main App: Received refresh-data event");
       loadAllNotes();
     });
 
-    const unlistenRefresh = listen("refresh-data", () => {
-      console.log("Main App: Received refresh-data event");
+    // Emitted by the backend when the local day rolls over
+    const unlistenDayChanged = listen<string>("day-changed", (event) => {
+      console.log("Main App: Day changed to", event.payload);
+      setCurrentDay(new Date(`${event.payload}T12:00:00`));
       loadAllNotes();
     });
     
@@ -51,15 +120,32 @@ function App() {
     const unlistenFocus = window.listen("tauri://focus", () => {
       loadAllNotes();
     });
+
+    // Files dropped while a note is being edited are attached to it
+    const unlistenDragDrop = window.onDragDropEvent(async (event) => {
+      const noteId = editingIdRef.current;
+      if (event.payload.type !== "drop" || noteId === null) {
+        return;
+      }
+      for (const path of event.payload.paths) {
+        try {
+          await invoke("attach_file", { noteId, path });
+        } catch (error) {
+          console.error("Failed to attach file:", error);
+        }
+      }
+      await loadAttachments(noteId);
+    });
     
     // Trigger fade-in animation
     setTimeout(() => setIsVisible(true), 100);
 
     return () => {
-      unlistenNoteUpdate.then(f => f());
       unlistenNotesUpdate.then(f => f());
       unlistenRefresh.then(f => f());
+      unlistenDayChanged.then(f => f());
       unlistenFocus.then(f => f());
+      unlistenDragDrop.then(f => f());
     };
   }, []);
 
@@ -67,16 +153,26 @@ function App() {
     try {
       setIsLoading(true);
       
-      // Load database and get all notes
-      const db = await Database.load("sqlite:daily-notes.db");
-      
-      const notes = await db.select<NoteEntry[]>(
-        "SELECT id, content, created_at, updated_at, is_quick_capture FROM notes WHERE content IS NOT NULL AND TRIM(content) != '' ORDER BY created_at DESC",
-        []
-      );
+      // Newest notes first, older pages are loaded while scrolling
+      const page = await invoke<NotePage>("get_notes", {
+        limit: Math.max(PAGE_SIZE, loadedCount.current),
+      });
       
-      console.log("Main App: Loaded all notes count:", notes.length);
-      setAllNotes(notes);
+      console.log("Main App: Loaded notes count:", page.notes.length);
+      loadedCount.current = page.notes.length;
+      setAllNotes(page.notes);
+      setPinned(page.pinned);
+      setOlderCursor(page.older);
+
+      // Open tasks from earlier days stay visible until they are closed
+      const today = await invoke<DayView>("get_day");
+      setOpenTasks(today.carried_over);
+
+      // Quick captures stay in the inbox until they are filed
+      setInbox(await invoke<NoteEntry[]>("get_inbox"));
+
+      // Cached in the backend until notes change
+      setStats(await invoke<Stats>("get_stats"));
     } catch (error) {
       console.error("Failed to load all notes:", error);
       setAllNotes([]);
@@ -85,6 +181,32 @@ function App() {
     }
   }
 
+  async function loadOlderNotes() {
+    if (!olderCursor || isLoadingMore) return;
+    try {
+      setIsLoadingMore(true);
+      const page = await invoke<NotePage>("get_notes", {
+        cursor: olderCursor,
+        direction: "older",
+        limit: PAGE_SIZE,
+      });
+      loadedCount.current += page.notes.length;
+      setAllNotes((current) => [...current, ...page.notes]);
+      setOlderCursor(page.older);
+    } catch (error) {
+      console.error("Failed to load older notes:", error);
+    } finally {
+      setIsLoadingMore(false);
+    }
+  }
+
+  function handleListScroll(event: React.UIEvent<HTMLDivElement>) {
+    const list = event.currentTarget;
+    if (!selectedDay && !isSearching && list.scrollHeight - list.scrollTop - list.clientHeight < 200) {
+      loadOlderNotes();
+    }
+  }
+
   // Handle keyboard shortcuts
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
@@ -97,6 +219,16 @@ function App() {
         event.preventDefault();
         loadAllNotes();
       }
+      // Cmd+Z / Cmd+Shift+Z undo and redo note changes, text fields keep their own undo
+      const target = event.target as HTMLElement | null;
+      const isEditing = target?.tagName === 'INPUT' || target?.tagName === 'TEXTAREA';
+      if ((event.metaKey || event.ctrlKey) && event.key.toLowerCase() === 'z' && !isEditing) {
+        event.preventDefault();
+        // The backend emits notes-updated, which reloads the list
+        invoke<boolean>(event.shiftKey ? "redo" : "undo").catch((error) => {
+          console.error("Failed to undo/redo:", error);
+        });
+      }
     };
 
     window.addEventListener('keydown', handleKeyDown);
@@ -105,27 +237,8 @@ function App() {
 
   async function openSettings() {
     try {
-      // Create or show settings window
-      const { WebviewWindow } = await import('@tauri-apps/api/webviewWindow');
-      
-      const existingWindow = await WebviewWindow.getByLabel('settings');
-      if (!existingWindow) {
-        const newWindow = new WebviewWindow('settings', {
-          url: 'settings.html',
-          title: 'Einstellungen - Daily App',
-          width: 400,
-          height: 500,
-          resizable: false,
-          maximizable: false,
-          minimizable: false,
-          center: true,
-        });
-        await newWindow.show();
-        await newWindow.setFocus();
-      } else {
-        await existingWindow.show();
-        await existingWindow.setFocus();
-      }
+      // The backend restores the window's last position and size
+      await invoke("open_settings_window");
     } catch (error) {
       console.error("Failed to open settings:", error);
     }
@@ -148,10 +261,39 @@ function App() {
     loadAllNotes();
   }, []);
 
+  // Reload the selected day whenever the list itself reloads
+  useEffect(() => {
+    if (selectedDay) {
+      invoke<DayView>("get_day", { date: selectedDay })
+        .then((view) => setDayNotes(view.notes))
+        .catch((error) => console.error("Failed to load day:", error));
+      invoke<NoteEntry[]>("get_backlinks", { day: selectedDay })
+        .then(setDayBacklinks)
+        .catch((error) => console.error("Failed to load backlinks:", error));
+    }
+  }, [selectedDay, allNotes]);
+
+  // Search covers archived notes too, unlike the list and the day view
+  const isSearching = showArchive || searchQuery.trim() !== "";
+  useEffect(() => {
+    if (isSearching) {
+      invoke<NoteEntry[]>("search_notes", { query: searchQuery, archived: showArchive ? true : null })
+        .then(setSearchResults)
+        .catch((error) => console.error("Failed to search notes:", error));
+    }
+  }, [searchQuery, showArchive, allNotes]);
+
+  // Pinned notes have their own section above the list
+  const visibleNotes = isSearching
+    ? searchResults
+    : selectedDay
+    ? dayNotes.filter((note) => note.pin_order === null).reverse()
+    : allNotes;
+
   async function deleteNote(id: number) {
     try {
-      const db = await Database.load("sqlite:daily-notes.db");
-      await db.execute("DELETE FROM notes WHERE id = $1", [id]);
+      // The backend emits notes-updated
+      await invoke("delete_note", { id });
       console.log("Note deleted successfully:", id);
       
       // Reload notes to update the list
@@ -161,27 +303,182 @@ function App() {
     }
   }
 
+  async function toggleTask(id: number) {
+    try {
+      // The backend emits notes-updated, which reloads the list
+      await invoke("toggle_task", { id });
+    } catch (error) {
+      console.error("Failed to toggle task:", error);
+    }
+  }
+
+  async function processCapture(command: "file_capture" | "capture_to_task" | "discard_capture", id: number) {
+    try {
+      // The backend emits notes-updated, which reloads the list and inbox
+      await invoke(command, { id });
+    } catch (error) {
+      console.error("Failed to process quick capture:", error);
+    }
+  }
+
+  async function togglePin(id: number) {
+    try {
+      // The backend emits notes-updated, which reloads the list
+      await invoke("toggle_pin", { id });
+    } catch (error) {
+      console.error("Failed to pin note:", error);
+    }
+  }
+
+  async function toggleStar(id: number) {
+    try {
+      await invoke("toggle_star", { id });
+    } catch (error) {
+      console.error("Failed to star note:", error);
+    }
+  }
+
+  async function setArchived(note: NoteEntry, archived: boolean) {
+    try {
+      // The backend emits notes-updated, which reloads the list and the search
+      await invoke(archived ? "archive_note" : "unarchive_note", { id: note.id });
+    } catch (error) {
+      console.error("Failed to archive note:", error);
+    }
+  }
+
+  async function movePinnedUp(index: number) {
+    if (index === 0) return;
+    const ids = pinned.map((note) => note.id);
+    [ids[index - 1], ids[index]] = [ids[index], ids[index - 1]];
+    try {
+      setPinned(await invoke<NoteEntry[]>("reorder_pinned", { ids }));
+    } catch (error) {
+      console.error("Failed to reorder pinned notes:", error);
+    }
+  }
+
+  // [[YYYY-MM-DD]] opens that day, [[note:123]] is shown as #123, [title](url)
+  // opens in the browser
+  function renderContent(content: string) {
+    return content.split(/(\[\[(?:note:\d+|\d{4}-\d{2}-\d{2})\]\]|\[[^[\]]+\]\(https?:\/\/[^\s)]+\))/g).map((part, index) => {
+      const day = part.match(/^\[\[(\d{4}-\d{2}-\d{2})\]\]$/);
+      if (day) {
+        return (
+          <button key={index} className="note-link" onClick={() => setSelectedDay(day[1])}>
+            {new Date(`${day[1]}T12:00:00`).toLocaleDateString(dateLocale)}
+          </button>
+        );
+      }
+      const note = part.match(/^\[\[note:(\d+)\]\]$/);
+      if (note) {
+        return <span key={index} className="note-link">#{note[1]}</span>;
+      }
+      // Written by clipboard captures
+      const link = part.match(/^\[([^[\]]+)\]\((https?:\/\/[^\s)]+)\)$/);
+      if (link) {
+        return (
+          <button key={index} className="note-link" title={link[2]} onClick={() => openUrl(link[2])}>
+            {link[1]}
+          </button>
+        );
+      }
+      return part;
+    });
+  }
+
+  function taskCheckbox(noteEntry: NoteEntry) {
+    if (!noteEntry.task_state) return null;
+    return (
+      <button
+        className={`task-checkbox ${noteEntry.task_state}`}
+        onClick={() => toggleTask(noteEntry.id)}
+        title={noteEntry.task_state === "open" ? "Erledigt" : "Wieder öffnen"}
+      >
+        {noteEntry.task_state === "done" ? "☑" : noteEntry.task_state === "cancelled" ? "☒" : "☐"}
+      </button>
+    );
+  }
+
+  async function saveReminder() {
+    if (remindingId && reminderWhen.trim()) {
+      try {
+        // Natural language like "morgen 9 Uhr" is resolved by the backend
+        await invoke("add_note_reminder", { noteId: remindingId, at: reminderWhen.trim() });
+        setRemindingId(null);
+        setReminderWhen("");
+      } catch (error) {
+        console.error("Failed to add reminder:", error);
+      }
+    }
+  }
+
   async function startEdit(noteEntry: NoteEntry) {
     setEditingId(noteEntry.id);
+    editingIdRef.current = noteEntry.id;
     setEditingContent(noteEntry.content);
+    setEditingWhen("");
+    await loadAttachments(noteEntry.id);
+  }
+
+  async function loadAttachments(noteId: number) {
+    try {
+      setAttachments(await invoke<Attachment[]>("list_attachments", { noteId }));
+    } catch (error) {
+      console.error("Failed to load attachments:", error);
+    }
+  }
+
+  // Pasted files, e.g. screenshots, are attached instead of inserted as text
+  async function attachPasted(noteId: number, files: File[]) {
+    for (const file of files) {
+      try {
+        const bytes = Array.from(new Uint8Array(await file.arrayBuffer()));
+        await invoke("attach_bytes", { noteId, name: file.name, bytes });
+      } catch (error) {
+        console.error("Failed to attach pasted file:", error);
+      }
+    }
+    await loadAttachments(noteId);
+  }
+
+  async function deleteAttachment(attachment: Attachment) {
+    try {
+      await invoke("delete_attachment", { id: attachment.id });
+      await loadAttachments(attachment.note_id);
+    } catch (error) {
+      console.error("Failed to delete attachment:", error);
+    }
+  }
+
+  async function exportDay(day: string) {
+    try {
+      const folder = await invoke<string>("export_day", { date: day });
+      console.log("Exported day to", folder);
+    } catch (error) {
+      console.error("Failed to export day:", error);
+    }
   }
 
   async function saveEdit() {
     if (editingId && editingContent.trim()) {
       try {
-        const db = await Database.load("sqlite:daily-notes.db");
-        const now = new Date().toISOString();
-        
-        await db.execute(
-          "UPDATE notes SET content = $1, updated_at = $2 WHERE id = $3",
-          [editingContent.trim(), now, editingId]
-        );
+        // The backend parses "gestern 16 Uhr" etc. and emits notes-updated
+        await invoke("update_note", {
+          request: {
+            id: editingId,
+            content: editingContent,
+            created_at: editingWhen.trim() || null,
+          },
+        });
         
         console.log("Note updated successfully:", editingId);
         
         // Reset editing state
         setEditingId(null);
+        editingIdRef.current = null;
         setEditingContent("");
+        setEditingWhen("");
         
         // Reload notes to update the list
         await loadAllNotes();
@@ -193,7 +490,9 @@ function App() {
 
   function cancelEdit() {
     setEditingId(null);
+    editingIdRef.current = null;
     setEditingContent("");
+    setEditingWhen("");
   }
 
   if (isLoading) {
@@ -224,32 +523,166 @@ function App() {
       <div className="content">
         <div className="header">
           <p className="date">{today}</p>
+          {stats && stats.current_streak > 0 && (
+            <p className="stats" title={`Ø ${stats.average_per_workday.toFixed(1)} Notizen pro Arbeitstag`}>
+              🔥 {stats.current_streak} {stats.current_streak === 1 ? "Arbeitstag" : "Arbeitstage"} in Folge
+            </p>
+          )}
         </div>
         
+        {pinned.length > 0 && (
+          <div className="note-area pinned">
+            <div className="section-title">
+              Angeheftet
+            </div>
+            <div className="notes-list">
+              {pinned.map((note, index) => (
+                <div key={note.id} className="note-entry">
+                  {taskCheckbox(note)}
+                  <div className={`note-content ${note.task_state ?? ''}`} title={note.content}>
+                    {note.starred && "★ "}{renderContent(note.content)}
+                  </div>
+                  <div className="note-actions">
+                    {index > 0 && (
+                      <button className="note-action-btn" title="Nach oben" onClick={() => movePinnedUp(index)}>↑</button>
+                    )}
+                    <button className="note-action-btn" title="Lösen" onClick={() => togglePin(note.id)}>📌</button>
+                  </div>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+
+        {inbox.length > 0 && (
+          <div className="note-area inbox">
+            <div className="section-title">
+              Inbox ({inbox.length})
+            </div>
+            <div className="notes-list">
+              {inbox.map((capture) => (
+                <div key={capture.id} className="note-entry">
+                  <div className="note-header">
+                    <span className="note-date">
+                      {new Date(capture.created_at).toLocaleDateString(dateLocale, {
+                        day: '2-digit',
+                        month: '2-digit'
+                      })}
+                    </span>
+                  </div>
+                  <div className="note-content" title={capture.content}>
+                    {capture.content}
+                  </div>
+                  <div className="note-actions">
+                    <button className="note-action-btn file" title="Ablegen" onClick={() => processCapture("file_capture", capture.id)}>📥</button>
+                    <button className="note-action-btn task" title="Als Aufgabe" onClick={() => processCapture("capture_to_task", capture.id)}>☐</button>
+                    <button className="note-action-btn delete" title="Verwerfen" onClick={() => processCapture("discard_capture", capture.id)}>🗑️</button>
+                  </div>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+
+        {openTasks.length > 0 && (
+          <div className="note-area carried-over">
+            <div className="section-title">
+              Offen aus den Vortagen
+            </div>
+            <div className="notes-list">
+              {openTasks.map((task) => (
+                <div key={task.id} className="note-entry">
+                  <div className="note-header">
+                    <span className="note-date">
+                      {new Date(task.created_at).toLocaleDateString(dateLocale, {
+                        day: '2-digit',
+                        month: '2-digit'
+                      })}
+                    </span>
+                  </div>
+                  {taskCheckbox(task)}
+                  <div className="note-content" title={task.content}>
+                    {renderContent(task.content)}
+                  </div>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+
+        <DayCalendar selectedDay={selectedDay} onSelectDay={setSelectedDay} dateLocale={dateLocale} />
+
+        <div className="search">
+          <input
+            className="search-input"
+            value={searchQuery}
+            onChange={(e) => setSearchQuery(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === 'Escape') {
+                e.stopPropagation();
+                setSearchQuery("");
+              }
+            }}
+            placeholder={showArchive ? "Archiv durchsuchen..." : "Suchen, auch im Archiv..."}
+          />
+          <button
+            className={`section-title-action ${showArchive ? 'active' : ''}`}
+            onClick={() => setShowArchive(!showArchive)}
+          >
+            Archiv
+          </button>
+        </div>
+
         <div className="note-area">
           <div className="section-title">
-            Alle Notizen
+            {isSearching ? (
+              showArchive ? "Archiv" : "Suchergebnisse"
+            ) : selectedDay ? (
+              <>
+                Notizen vom {new Date(`${selectedDay}T12:00:00`).toLocaleDateString(dateLocale)}
+                <button className="section-title-action" onClick={() => exportDay(selectedDay)}>
+                  Exportieren
+                </button>
+                <button className="section-title-action" onClick={() => setSelectedDay(null)}>
+                  Alle anzeigen
+                </button>
+              </>
+            ) : (
+              "Alle Notizen"
+            )}
           </div>
-          <div className="notes-list">
-            {isLoading ? (
+          {!isSearching && selectedDay && dayBacklinks.length > 0 && (
+            <div className="backlinks">
+              Verlinkt von:
+              {dayBacklinks.map((note) => (
+                <span key={note.id} className="backlink" title={note.content}>
+                  {note.content}
+                </span>
+              ))}
+            </div>
+          )}
+          <div className="notes-list" onScroll={handleListScroll}>
+            {isLoading && visibleNotes.length === 0 ? (
               <div className="notes-list-loading">Laden...</div>
-            ) : allNotes.length === 0 ? (
-              <div className="notes-list-empty">Noch keine Notizen vorhanden</div>
+            ) : visibleNotes.length === 0 ? (
+              <div className="notes-list-empty">
+                {isSearching ? "Keine Treffer" : "Noch keine Notizen vorhanden"}
+              </div>
             ) : (
-              allNotes.map((noteEntry) => (
+              visibleNotes.map((noteEntry) => (
                 <div key={noteEntry.id} className="note-entry">
                   {editingId === noteEntry.id ? (
                     // Edit mode
                     <>
                       <div className="note-header">
                         <span className="note-date">
-                          {new Date(noteEntry.created_at).toLocaleDateString('de-DE', {
+                          {new Date(noteEntry.created_at).toLocaleDateString(dateLocale, {
                             day: '2-digit',
                             month: '2-digit'
                           })}
                         </span>
                         <span className="note-time">
-                          {new Date(noteEntry.created_at).toLocaleTimeString('de-DE', {
+                          {new Date(noteEntry.created_at).toLocaleTimeString(dateLocale, {
                             hour: '2-digit',
                             minute: '2-digit'
                           })}
@@ -259,6 +692,13 @@ function App() {
                         className="note-edit-input"
                         value={editingContent}
                         onChange={(e) => setEditingContent(e.target.value)}
+                        onPaste={(e) => {
+                          const files = Array.from(e.clipboardData.files);
+                          if (files.length > 0) {
+                            e.preventDefault();
+                            attachPasted(noteEntry.id, files);
+                          }
+                        }}
                         onKeyDown={(e) => {
                           if (e.key === 'Enter') {
                             saveEdit();
@@ -268,6 +708,32 @@ function App() {
                         }}
                         autoFocus
                       />
+                      <input
+                        className="note-edit-when"
+                        value={editingWhen}
+                        onChange={(e) => setEditingWhen(e.target.value)}
+                        onKeyDown={(e) => {
+                          if (e.key === 'Enter') {
+                            saveEdit();
+                          } else if (e.key === 'Escape') {
+                            cancelEdit();
+                          }
+                        }}
+                        placeholder="Zeitpunkt, z.B. gestern 16 Uhr"
+                      />
+                      <div className="attachments" title="Dateien hierher ziehen oder einfügen">
+                        {attachments.map((attachment) => (
+                          <span key={attachment.id} className="attachment">
+                            <button
+                              className="attachment-name"
+                              onClick={() => invoke("open_attachment", { id: attachment.id })}
+                            >
+                              📎 {attachment.name}
+                            </button>
+                            <button className="attachment-delete" onClick={() => deleteAttachment(attachment)}>✕</button>
+                          </span>
+                        ))}
+                      </div>
                       <div className="note-actions">
                         <button className="note-action-btn save" onClick={saveEdit}>✓</button>
                         <button className="note-action-btn cancel" onClick={cancelEdit}>✕</button>
@@ -278,22 +744,47 @@ function App() {
                     <>
                       <div className="note-header">
                         <span className="note-date">
-                          {new Date(noteEntry.created_at).toLocaleDateString('de-DE', {
+                          {new Date(noteEntry.created_at).toLocaleDateString(dateLocale, {
                             day: '2-digit',
                             month: '2-digit'
                           })}
                         </span>
                         <span className="note-time">
-                          {new Date(noteEntry.created_at).toLocaleTimeString('de-DE', {
+                          {new Date(noteEntry.created_at).toLocaleTimeString(dateLocale, {
                             hour: '2-digit',
                             minute: '2-digit'
                           })}
                         </span>
                       </div>
-                      <div className="note-content" title={noteEntry.content}>
-                        {noteEntry.content}
+                      {taskCheckbox(noteEntry)}
+                      <div className={`note-content ${noteEntry.task_state ?? ''}`} title={noteEntry.content}>
+                        {noteEntry.starred && "★ "}{renderContent(noteEntry.content)}
                       </div>
+                      {remindingId === noteEntry.id && (
+                        <input
+                          className="note-edit-when"
+                          value={reminderWhen}
+                          onChange={(e) => setReminderWhen(e.target.value)}
+                          onKeyDown={(e) => {
+                            if (e.key === 'Enter') {
+                              saveReminder();
+                            } else if (e.key === 'Escape') {
+                              setRemindingId(null);
+                            }
+                          }}
+                          placeholder="Erinnern, z.B. morgen 9 Uhr"
+                          autoFocus
+                        />
+                      )}
                       <div className="note-actions">
+                        <button className="note-action-btn star" title={noteEntry.starred ? "Stern entfernen" : "Markieren"} onClick={() => toggleStar(noteEntry.id)}>{noteEntry.starred ? "★" : "☆"}</button>
+                        <button className="note-action-btn pin" title="Anheften" onClick={() => togglePin(noteEntry.id)}>📌</button>
+                        <button className="note-action-btn remind" onClick={() => { setRemindingId(noteEntry.id); setReminderWhen(""); }}>⏰</button>
+                        {noteEntry.archived_at ? (
+                          <button className="note-action-btn archive" title="Wiederherstellen" onClick={() => setArchived(noteEntry, false)}>↩</button>
+                        ) : (
+                          <button className="note-action-btn archive" title="Archivieren" onClick={() => setArchived(noteEntry, true)}>🗄️</button>
+                        )}
                         <button className="note-action-btn edit" onClick={() => startEdit(noteEntry)}>✏️</button>
                         <button className="note-action-btn delete" onClick={() => deleteNote(noteEntry.id)}>🗑️</button>
                       </div>
@@ -302,11 +793,12 @@ function App() {
                 </div>
               ))
             )}
+            {isLoadingMore && <div className="notes-list-loading">Laden...</div>}
           </div>
         </div>
         
         <div className="footer">
-          <small>ESC schließen • ⌘+R aktualisieren • ⌘+Shift+Space Quick Capture • v0.5.0</small>
+          <small>ESC schließen • ⌘+R aktualisieren • ⌘+Z rückgängig • ⌘+Shift+Space Quick Capture • v0.5.0</small>
         </div>
       </div>
     </div>
@@ -0,0 +1,88 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface DayCount {
+  day: string;
+  count: number;
+}
+
+interface DayCalendarProps {
+  selectedDay: string | null;
+  onSelectDay: (day: string | null) => void;
+  dateLocale: string;
+}
+
+// Month heatmap, clicking a day shows only that day's notes
+function DayCalendar({ selectedDay, onSelectDay, dateLocale }: DayCalendarProps) {
+  const [month, setMonth] = useState(() => {
+    const now = new Date();
+    return { year: now.getFullYear(), month: now.getMonth() + 1 };
+  });
+  const [counts, setCounts] = useState<DayCount[]>([]);
+
+  useEffect(() => {
+    loadCounts();
+    const unlistenNotesUpdate = listen("notes-updated", () => loadCounts());
+    return () => {
+      unlistenNotesUpdate.then(f => f());
+    };
+  }, [month]);
+
+  async function loadCounts() {
+    try {
+      setCounts(await invoke<DayCount[]>("get_heatmap", month));
+    } catch (error) {
+      console.error("Failed to load calendar:", error);
+    }
+  }
+
+  function shiftMonth(delta: number) {
+    const date = new Date(month.year, month.month - 1 + delta, 1);
+    setMonth({ year: date.getFullYear(), month: date.getMonth() + 1 });
+  }
+
+  const max = Math.max(1, ...counts.map((c) => c.count));
+  // Empty cells so the first day lands on its weekday column
+  const offset = (new Date(month.year, month.month - 1, 1).getDay() + 6) % 7;
+  const title = new Date(month.year, month.month - 1, 1).toLocaleDateString(dateLocale, {
+    month: 'long',
+    year: 'numeric'
+  });
+
+  // 1 January 2024 was a Monday
+  const weekdayLabels = Array.from({ length: 7 }, (_, i) =>
+    new Date(2024, 0, 1 + i).toLocaleDateString(dateLocale, { weekday: 'short' })
+  );
+
+  return (
+    <div className="day-calendar">
+      <div className="day-calendar-header">
+        <button onClick={() => shiftMonth(-1)} title="Vorheriger Monat">‹</button>
+        <span>{title}</span>
+        <button onClick={() => shiftMonth(1)} title="Nächster Monat">›</button>
+      </div>
+      <div className="day-calendar-grid">
+        {weekdayLabels.map((label) => (
+          <span key={label} className="day-calendar-weekday">{label}</span>
+        ))}
+        {Array.from({ length: offset }, (_, i) => (
+          <span key={`empty-${i}`} />
+        ))}
+        {counts.map(({ day, count }) => (
+          <button
+            key={day}
+            className={`day-calendar-day ${day === selectedDay ? "selected" : ""}`}
+            style={{ "--level": count / max } as React.CSSProperties}
+            onClick={() => onSelectDay(day === selectedDay ? null : day)}
+            title={`${count} Notizen`}
+          >
+            {Number(day.slice(8))}
+          </button>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+export default DayCalendar;
@@ -1,7 +1,8 @@
 import { useState, useEffect } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { listen } from "@tauri-apps/api/event";
-import Database from "@tauri-apps/plugin-sql";
+import { invoke } from "@tauri-apps/api/core";
+import { useDateLocale } from "./useDateLocale";
 import "./QuickCapture.css";
 
 interface NoteEntry {
@@ -10,6 +11,28 @@ interface NoteEntry {
   created_at: string;
   updated_at: string;
   is_quick_capture: boolean;
+  pin_order: number | null;
+}
+
+interface NotesChanged {
+  kind: "created" | "updated" | "deleted" | "bulk";
+  ids: number[];
+  days: string[];
+}
+
+// Clipboard content waiting for confirmation, see clipboard.rs
+type ClipPreview =
+  | { kind: "text"; text: string }
+  | { kind: "url"; url: string }
+  | { kind: "image"; width: number; height: number };
+
+interface ParsedCapture {
+  content: string;
+  section: "done" | "todo" | "blocker" | null;
+  tags: string[];
+  date: string | null;
+  priority: "low" | "medium" | "high" | null;
+  duration_minutes: number | null;
 }
 
 function QuickCapture() {
@@ -17,10 +40,32 @@ function QuickCapture() {
   const [recentNotes, setRecentNotes] = useState<NoteEntry[]>([]);
   const [isLoading, setIsLoading] = useState(true);
   const [isClosing, setIsClosing] = useState(false);
+  const [preview, setPreview] = useState<ParsedCapture | null>(null);
+  const [clip, setClip] = useState<ClipPreview | null>(null);
+  // Configured in settings, see retention.rs
+  const [visibleHours, setVisibleHours] = useState(48);
+  const dateLocale = useDateLocale();
+
+  // Parse on every keystroke so tokens show up before saving
+  useEffect(() => {
+    if (!input.trim()) {
+      setPreview(null);
+      return;
+    }
+    invoke<ParsedCapture>("preview_capture", { input })
+      .then(setPreview)
+      .catch((error) => console.error("Failed to preview capture:", error));
+  }, [input, clip]);
 
   useEffect(() => {
     // Load data immediately when window is created
     loadRecentNotes();
+    // The clipboard shortcut may have opened this window before it listened
+    loadPendingClip();
+
+    const unlistenClipboard = listen<ClipPreview>("clipboard-capture", (event) => {
+      setClip(event.payload);
+    });
     
     // Listen for multiple refresh events from backend
     const unlistenRefresh = listen("refresh-data", () => {
@@ -33,15 +78,12 @@ function QuickCapture() {
       loadRecentNotes();
     });
 
-    const unlistenNotesUpdated = listen("notes-updated", () => {
+    // Every note change arrives as notes-updated, see events.rs
+    const unlistenNotesUpdated = listen<NotesChanged>("notes-updated", () => {
       console.log("Quick Capture: Notes updated event received");
       loadRecentNotes();
     });
 
-    const unlistenNoteCreated = listen("note-created", () => {
-      console.log("Quick Capture: Note created event received");
-      loadRecentNotes();
-    });
     
     // Listen for window focus/visibility to reload data
     const window = getCurrentWindow();
@@ -92,7 +134,7 @@ function QuickCapture() {
       unlistenRefresh.then(f => f());
       unlistenForceRefresh.then(f => f());
       unlistenNotesUpdated.then(f => f());
-      unlistenNoteCreated.then(f => f());
+      unlistenClipboard.then(f => f());
       unlistenFocus.then(f => f());
       unlistenShow.then(f => f());
       clearInterval(visibilityCheck);
@@ -104,17 +146,12 @@ function QuickCapture() {
       setIsLoading(true);
       console.log("Quick Capture: Loading recent notes...");
       
-      const db = await Database.load("sqlite:daily-notes.db");
-      
-      // Get notes from last 48 hours
-      const now = new Date();
-      const fortyEightHoursAgo = new Date(now.getTime() - (48 * 60 * 60 * 1000));
-      const cutoffTime = fortyEightHoursAgo.toISOString();
-      
-      const notes = await db.select<NoteEntry[]>(
-        "SELECT id, content, created_at, updated_at, is_quick_capture FROM notes WHERE created_at >= $1 AND content IS NOT NULL AND TRIM(content) != '' ORDER BY created_at DESC LIMIT 50",
-        [cutoffTime]
-      );
+      // The backend applies the configured window and row limit
+      const [notes, settings] = await Promise.all([
+        invoke<NoteEntry[]>("get_recent_notes"),
+        invoke<{ capture_retention: { visible_hours: number } }>("get_settings"),
+      ]);
+      setVisibleHours(settings.capture_retention.visible_hours);
       
       console.log("Quick Capture: Loaded recent notes count:", notes.length);
       setRecentNotes(notes);
@@ -125,17 +162,42 @@ function QuickCapture() {
     }
   }
 
+  async function loadPendingClip() {
+    try {
+      setClip(await invoke<ClipPreview | null>("get_pending_clip"));
+    } catch (error) {
+      console.error("Failed to load clipboard capture:", error);
+    }
+  }
+
+  async function confirmClip() {
+    try {
+      await invoke("save_pending_clip");
+      setClip(null);
+      await loadRecentNotes();
+      setTimeout(async () => {
+        await closeWindow();
+      }, 100);
+    } catch (error) {
+      console.error("Failed to save clipboard capture:", error);
+    }
+  }
+
+  async function discardClip() {
+    try {
+      await invoke("discard_pending_clip");
+    } catch (error) {
+      console.error("Failed to discard clipboard capture:", error);
+    }
+    setClip(null);
+    closeWindow();
+  }
+
   async function handleSubmit() {
     if (input.trim()) {
       try {
-        const db = await Database.load("sqlite:daily-notes.db");
-        const now = new Date().toISOString();
-        
-        // Create new quick capture note
-        await db.execute(
-          "INSERT INTO notes (content, created_at, updated_at, is_quick_capture) VALUES ($1, $2, $3, $4)",
-          [input.trim(), now, now, true]
-        );
+        // Parsed and stored by the backend, which also emits notes-updated
+        await invoke("save_capture", { input });
         
         // Reload recent notes to show the new entry
         await loadRecentNotes();
@@ -167,7 +229,15 @@ function QuickCapture() {
   // Handle keyboard shortcuts
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
-      if (event.key === 'Escape') {
+      // A pending clip takes Enter and Escape until it is saved or discarded
+      if (clip && (event.key === 'Enter' || event.key === 'Escape')) {
+        event.preventDefault();
+        if (event.key === 'Enter') {
+          confirmClip();
+        } else {
+          discardClip();
+        }
+      } else if (event.key === 'Escape') {
         event.preventDefault();
         setInput(""); // Clear input first
         closeWindow();
@@ -188,12 +258,12 @@ function QuickCapture() {
       const diffHours = (now.getTime() - date.getTime()) / (1000 * 60 * 60);
       
       if (diffHours < 24) {
-        return date.toLocaleString('de-DE', {
+        return date.toLocaleString(dateLocale, {
           hour: '2-digit',
           minute: '2-digit',
         });
       } else {
-        return date.toLocaleString('de-DE', {
+        return date.toLocaleString(dateLocale, {
           day: '2-digit',
           month: '2-digit',
           hour: '2-digit',
@@ -205,6 +275,20 @@ function QuickCapture() {
     }
   };
 
+  const formatDuration = (minutes: number) => {
+    const hours = Math.floor(minutes / 60);
+    const rest = minutes % 60;
+    if (hours === 0) return `${rest}m`;
+    return rest === 0 ? `${hours}h` : `${hours}h${rest}m`;
+  };
+
+  const hasTokens = (parsed: ParsedCapture) =>
+    parsed.section !== null ||
+    parsed.tags.length > 0 ||
+    parsed.date !== null ||
+    parsed.priority !== null ||
+    parsed.duration_minutes !== null;
+
   const truncateText = (text: string, maxLength: number = 60) => {
     if (text.length <= maxLength) return text;
     return text.substring(0, maxLength) + '...';
@@ -223,10 +307,41 @@ function QuickCapture() {
           />
         </div>
 
-        {/* All Notes from last 48h */}
+        {clip && (
+          <div className="clip-preview">
+            <span className="note-badge">Zwischenablage</span>
+            <span className="capture-preview-content">
+              {clip.kind === "text" && truncateText(clip.text, 120)}
+              {clip.kind === "url" && `🔗 ${clip.url}`}
+              {clip.kind === "image" && `🖼 Bild ${clip.width}×${clip.height}`}
+            </span>
+            <span className="clip-preview-hint">⏎ Speichern • ⎋ Verwerfen</span>
+          </div>
+        )}
+
+        {preview && hasTokens(preview) && (
+          <div className="capture-preview">
+            {preview.section && (
+              <span className="note-badge">/{preview.section}</span>
+            )}
+            {preview.date && <span className="note-badge">@{preview.date}</span>}
+            {preview.priority && (
+              <span className="note-badge">!{preview.priority}</span>
+            )}
+            {preview.duration_minutes !== null && (
+              <span className="note-badge">~{formatDuration(preview.duration_minutes)}</span>
+            )}
+            {preview.tags.map((tag) => (
+              <span key={tag} className="note-badge">#{tag}</span>
+            ))}
+            <span className="capture-preview-content">{preview.content}</span>
+          </div>
+        )}
+
+        {/* Notes of the configured recent window */}
         <div className="recent-notes">
           <div className="recent-notes-header">
-            <div className="recent-notes-title">Letzte 48h</div>
+            <div className="recent-notes-title">Letzte {visibleHours}h</div>
             <button 
               className="reload-button" 
               onClick={loadRecentNotes}
@@ -241,7 +356,7 @@ function QuickCapture() {
           ) : (
             <div className="notes-list">
               {recentNotes.length === 0 ? (
-                <div className="no-notes">Keine Notizen in den letzten 48h</div>
+                <div className="no-notes">Keine Notizen in den letzten {visibleHours}h</div>
               ) : (
                 recentNotes.map((note) => (
                   <div key={note.id} className="note-line">
@@ -251,6 +366,9 @@ function QuickCapture() {
                     <span className="note-content">
                       {truncateText(note.content)}
                     </span>
+                    {note.pin_order !== null && (
+                      <span className="note-badge pinned">📌</span>
+                    )}
                     {note.is_quick_capture && (
                       <span className="note-badge quick">Quick</span>
                     )}
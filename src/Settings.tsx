@@ -4,14 +4,327 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import "./Settings.css";
 
+interface Reminder {
+  id: number;
+  kind: "standup" | "end_of_day" | "note";
+  time_of_day: string | null;
+  workdays_only: boolean;
+  enabled: boolean;
+}
+
+interface WorkCalendar {
+  workdays: string[];
+  holidays: string[];
+}
+
+interface SprintConfig {
+  length_weeks: number;
+  start_weekday: string;
+  anchor: string | null;
+}
+
+interface CaptureRetention {
+  visible_hours: number;
+  visible_limit: number;
+  action: "keep" | "archive" | "delete";
+  after_days: number;
+}
+
+interface ArchivePolicy {
+  after_days: number | null;
+  keep_pinned: boolean;
+  keep_tagged: boolean;
+}
+
+interface RetentionPreview {
+  visible: number;
+  affected: number;
+}
+
+const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const REMINDER_NAMES: Record<string, string> = {
+  standup: "Write your standup",
+  end_of_day: "Log what you did",
+};
+
 function Settings() {
   const [isVisible, setIsVisible] = useState(false);
+  const [launchAtLogin, setLaunchAtLogin] = useState(false);
+  const [language, setLanguage] = useState<string>("system");
+  const [dayStartHour, setDayStartHour] = useState(0);
+  const [autoFileHours, setAutoFileHours] = useState<number | null>(null);
+  const [clipboardSilent, setClipboardSilent] = useState(false);
+  const [fetchLinkTitles, setFetchLinkTitles] = useState(false);
+  const [retention, setRetention] = useState<CaptureRetention | null>(null);
+  // Edited here and previewed until applied
+  const [retentionDraft, setRetentionDraft] = useState<CaptureRetention | null>(null);
+  const [retentionPreview, setRetentionPreview] = useState<RetentionPreview | null>(null);
+  const [archivePolicy, setArchivePolicy] = useState<ArchivePolicy | null>(null);
+  const [archiveDraft, setArchiveDraft] = useState<ArchivePolicy | null>(null);
+  // Notes the draft would archive right now
+  const [archivePreview, setArchivePreview] = useState<number | null>(null);
+  const [placements, setPlacements] = useState<Record<string, string>>({});
+  const [reminders, setReminders] = useState<Reminder[]>([]);
+  const [calendar, setCalendar] = useState<WorkCalendar>({ workdays: [], holidays: [] });
+  const [holidaysText, setHolidaysText] = useState("");
+  const [sprint, setSprint] = useState<SprintConfig>({
+    length_weeks: 2,
+    start_weekday: "Mon",
+    anchor: null,
+  });
 
   useEffect(() => {
     // Trigger fade-in animation
     setTimeout(() => setIsVisible(true), 100);
+    loadLaunchAtLogin();
+    loadLanguage();
+    loadPlacements();
+    loadReminders();
+    loadCalendar();
+    loadSprint();
   }, []);
 
+  async function loadReminders() {
+    try {
+      const all = await invoke<Reminder[]>("list_reminders");
+      setReminders(all.filter((reminder) => reminder.kind !== "note"));
+    } catch (error) {
+      console.error("Failed to load reminders:", error);
+    }
+  }
+
+  async function changeReminder(reminder: Reminder) {
+    try {
+      const updated = await invoke<Reminder>("update_reminder", {
+        id: reminder.id,
+        timeOfDay: reminder.time_of_day,
+        workdaysOnly: reminder.workdays_only,
+        enabled: reminder.enabled,
+      });
+      setReminders((current) => current.map((r) => (r.id === updated.id ? updated : r)));
+    } catch (error) {
+      console.error("Failed to update reminder:", error);
+    }
+  }
+
+  async function loadCalendar() {
+    try {
+      const loaded = await invoke<WorkCalendar>("get_work_calendar");
+      setCalendar(loaded);
+      setHolidaysText(loaded.holidays.join("\n"));
+    } catch (error) {
+      console.error("Failed to load work calendar:", error);
+    }
+  }
+
+  async function saveCalendar(next: WorkCalendar) {
+    try {
+      await invoke("set_work_calendar", { calendar: next });
+      setCalendar(next);
+    } catch (error) {
+      console.error("Failed to save work calendar:", error);
+    }
+  }
+
+  function toggleWorkday(day: string, enabled: boolean) {
+    const workdays = enabled
+      ? WEEKDAYS.filter((d) => d === day || calendar.workdays.includes(d))
+      : calendar.workdays.filter((d) => d !== day);
+    saveCalendar({ ...calendar, workdays });
+  }
+
+  function saveHolidays() {
+    // One YYYY-MM-DD date per line, anything else is dropped
+    const holidays = holidaysText
+      .split("\n")
+      .map((line) => line.trim())
+      .filter((line) => /^\d{4}-\d{2}-\d{2}$/.test(line));
+    saveCalendar({ ...calendar, holidays });
+    setHolidaysText(holidays.join("\n"));
+  }
+
+  async function loadSprint() {
+    try {
+      setSprint(await invoke<SprintConfig>("get_sprint"));
+    } catch (error) {
+      console.error("Failed to load sprint settings:", error);
+    }
+  }
+
+  async function saveSprint(next: SprintConfig) {
+    try {
+      await invoke("set_sprint", { sprint: next });
+      setSprint(next);
+    } catch (error) {
+      console.error("Failed to save sprint settings:", error);
+    }
+  }
+
+  const placementWindows = [
+    { label: "main", name: "Notes window" },
+    { label: "quick-capture", name: "Quick Capture" },
+    { label: "settings", name: "Settings" },
+  ];
+
+  async function loadPlacements() {
+    try {
+      const entries = await Promise.all(
+        placementWindows.map(async ({ label }) =>
+          [label, await invoke<string>("get_window_placement", { label })] as const
+        )
+      );
+      setPlacements(Object.fromEntries(entries));
+    } catch (error) {
+      console.error("Failed to load window placement:", error);
+    }
+  }
+
+  async function changePlacement(label: string, placement: string) {
+    try {
+      await invoke("set_window_placement", { label, placement });
+      setPlacements((current) => ({ ...current, [label]: placement }));
+    } catch (error) {
+      console.error("Failed to change window placement:", error);
+    }
+  }
+
+  async function loadLanguage() {
+    try {
+      const settings = await invoke<{
+        language: string | null;
+        day_start_hour: number;
+        inbox_auto_file_hours: number | null;
+        clipboard_capture_silent: boolean;
+        fetch_link_titles: boolean;
+        capture_retention: CaptureRetention;
+        archive: ArchivePolicy;
+      }>("get_settings");
+      setLanguage(settings.language ?? "system");
+      setDayStartHour(settings.day_start_hour);
+      setAutoFileHours(settings.inbox_auto_file_hours);
+      setClipboardSilent(settings.clipboard_capture_silent);
+      setFetchLinkTitles(settings.fetch_link_titles);
+      setRetention(settings.capture_retention);
+      setRetentionDraft(settings.capture_retention);
+      setArchivePolicy(settings.archive);
+      setArchiveDraft(settings.archive);
+    } catch (error) {
+      console.error("Failed to load language:", error);
+    }
+  }
+
+  async function changeLanguage(value: string) {
+    try {
+      // Tray menu and window titles are rebuilt by the backend
+      await invoke("set_language", { language: value === "system" ? null : value });
+      setLanguage(value);
+    } catch (error) {
+      console.error("Failed to change language:", error);
+    }
+  }
+
+  async function changeDayStartHour(hour: number) {
+    try {
+      // The backend emits day-changed if this moves "today"
+      await invoke("set_day_start_hour", { hour });
+      setDayStartHour(hour);
+    } catch (error) {
+      console.error("Failed to change day start:", error);
+    }
+  }
+
+  async function changeAutoFileHours(hours: number | null) {
+    try {
+      await invoke("set_inbox_auto_file_hours", { hours });
+      setAutoFileHours(hours);
+    } catch (error) {
+      console.error("Failed to change inbox auto-filing:", error);
+    }
+  }
+
+  async function editRetention(change: Partial<CaptureRetention>) {
+    if (!retentionDraft) return;
+    const next = { ...retentionDraft, ...change };
+    setRetentionDraft(next);
+    try {
+      setRetentionPreview(await invoke<RetentionPreview>("preview_capture_retention", { retention: next }));
+    } catch (error) {
+      console.error("Failed to preview capture retention:", error);
+    }
+  }
+
+  async function applyRetention() {
+    if (!retentionDraft) return;
+    try {
+      // Expired captures are archived or deleted on the next scheduler tick
+      await invoke("set_capture_retention", { retention: retentionDraft });
+      setRetention(retentionDraft);
+      setRetentionPreview(null);
+    } catch (error) {
+      console.error("Failed to change capture retention:", error);
+    }
+  }
+
+  async function editArchive(change: Partial<ArchivePolicy>) {
+    if (!archiveDraft) return;
+    const next = { ...archiveDraft, ...change };
+    setArchiveDraft(next);
+    try {
+      setArchivePreview(await invoke<number>("preview_archive_policy", { policy: next }));
+    } catch (error) {
+      console.error("Failed to preview archive policy:", error);
+    }
+  }
+
+  async function applyArchive() {
+    if (!archiveDraft) return;
+    try {
+      // Old notes are archived on the next scheduler tick
+      await invoke("set_archive_policy", { policy: archiveDraft });
+      setArchivePolicy(archiveDraft);
+      setArchivePreview(null);
+    } catch (error) {
+      console.error("Failed to change archive policy:", error);
+    }
+  }
+
+  async function changeClipboardSilent(silent: boolean) {
+    try {
+      await invoke("set_clipboard_capture_silent", { silent });
+      setClipboardSilent(silent);
+    } catch (error) {
+      console.error("Failed to change clipboard capture:", error);
+    }
+  }
+
+  async function changeFetchLinkTitles(fetch: boolean) {
+    try {
+      await invoke("set_fetch_link_titles", { fetch });
+      setFetchLinkTitles(fetch);
+    } catch (error) {
+      console.error("Failed to change link titles:", error);
+    }
+  }
+
+  async function loadLaunchAtLogin() {
+    try {
+      // Reports the actual OS state, not just the stored preference
+      setLaunchAtLogin(await invoke<boolean>("get_launch_at_login"));
+    } catch (error) {
+      console.error("Failed to load launch at login state:", error);
+    }
+  }
+
+  async function toggleLaunchAtLogin(enabled: boolean) {
+    try {
+      setLaunchAtLogin(await invoke<boolean>("set_launch_at_login", { enabled }));
+    } catch (error) {
+      console.error("Failed to update launch at login:", error);
+      await loadLaunchAtLogin();
+    }
+  }
+
   const appVersion = "0.5.0";
   const authorName = "Andre Bellmann";
   const authorEmail = "andre@andre-bellmann.de";
@@ -94,6 +407,288 @@ function Settings() {
           </div>
         </section>
 
+        {/* General Section */}
+        <section className="settings-section">
+          <h2>General</h2>
+          <label className="toggle-item">
+            <input
+              type="checkbox"
+              checked={launchAtLogin}
+              onChange={(e) => toggleLaunchAtLogin(e.target.checked)}
+            />
+            <span>Start at login</span>
+          </label>
+          <label className="toggle-item">
+            <span>Day starts at</span>
+            <select
+              value={dayStartHour}
+              onChange={(e) => changeDayStartHour(Number(e.target.value))}
+            >
+              {Array.from({ length: 24 }, (_, hour) => (
+                <option key={hour} value={hour}>
+                  {String(hour).padStart(2, "0")}:00
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="toggle-item">
+            <span>File quick captures after</span>
+            <select
+              value={autoFileHours ?? ""}
+              onChange={(e) => changeAutoFileHours(e.target.value ? Number(e.target.value) : null)}
+            >
+              <option value="">Never</option>
+              {[4, 8, 24, 48, 72, 168].map((hours) => (
+                <option key={hours} value={hours}>
+                  {hours < 24 ? `${hours} hours` : `${hours / 24} ${hours === 24 ? "day" : "days"}`}
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="toggle-item">
+            <input
+              type="checkbox"
+              checked={clipboardSilent}
+              onChange={(e) => changeClipboardSilent(e.target.checked)}
+            />
+            <span>Capture clipboard without asking</span>
+          </label>
+          <label className="toggle-item">
+            <input
+              type="checkbox"
+              checked={fetchLinkTitles}
+              onChange={(e) => changeFetchLinkTitles(e.target.checked)}
+            />
+            <span>Fetch page titles of captured links</span>
+          </label>
+          <label className="toggle-item">
+            <span>Language</span>
+            <select value={language} onChange={(e) => changeLanguage(e.target.value)}>
+              <option value="system">System</option>
+              <option value="de">Deutsch</option>
+              <option value="en">English</option>
+            </select>
+          </label>
+        </section>
+
+        {/* Quick Capture Retention Section */}
+        {retentionDraft && (
+          <section className="settings-section">
+            <h2>Quick Capture History</h2>
+            <label className="toggle-item">
+              <span>Show the last</span>
+              <select
+                value={retentionDraft.visible_hours}
+                onChange={(e) => editRetention({ visible_hours: Number(e.target.value) })}
+              >
+                {[12, 24, 48, 72, 168].map((hours) => (
+                  <option key={hours} value={hours}>{hours} hours</option>
+                ))}
+              </select>
+              <span>at most</span>
+              <select
+                value={retentionDraft.visible_limit}
+                onChange={(e) => editRetention({ visible_limit: Number(e.target.value) })}
+              >
+                {[20, 50, 100, 200].map((limit) => (
+                  <option key={limit} value={limit}>{limit} notes</option>
+                ))}
+              </select>
+            </label>
+            <label className="toggle-item">
+              <span>Old captures</span>
+              <select
+                value={retentionDraft.action}
+                onChange={(e) => editRetention({ action: e.target.value as CaptureRetention["action"] })}
+              >
+                <option value="keep">Keep</option>
+                <option value="archive">Archive</option>
+                <option value="delete">Delete</option>
+              </select>
+              {retentionDraft.action !== "keep" && (
+                <>
+                  <span>after</span>
+                  <select
+                    value={retentionDraft.after_days}
+                    onChange={(e) => editRetention({ after_days: Number(e.target.value) })}
+                  >
+                    {[7, 30, 90, 180, 365].map((days) => (
+                      <option key={days} value={days}>{days} days</option>
+                    ))}
+                  </select>
+                </>
+              )}
+            </label>
+            {retentionPreview && JSON.stringify(retentionDraft) !== JSON.stringify(retention) && (
+              <div className="toggle-item">
+                <span>
+                  Quick capture would show {retentionPreview.visible} notes
+                  {retentionDraft.action !== "keep" &&
+                    `, ${retentionPreview.affected} captures would be ${retentionDraft.action === "archive" ? "archived" : "deleted"}`}
+                  . Pinned, starred and filed captures and open tasks are kept.
+                </span>
+                <button className="website-button" onClick={applyRetention}>Apply</button>
+              </div>
+            )}
+          </section>
+        )}
+
+        {/* Archive Section */}
+        {archiveDraft && (
+          <section className="settings-section">
+            <h2>Archive</h2>
+            <label className="toggle-item">
+              <span>Archive notes older than</span>
+              <select
+                value={archiveDraft.after_days ?? ""}
+                onChange={(e) => editArchive({ after_days: e.target.value ? Number(e.target.value) : null })}
+              >
+                <option value="">Never</option>
+                {[30, 90, 180, 365, 730].map((days) => (
+                  <option key={days} value={days}>{days} days</option>
+                ))}
+              </select>
+            </label>
+            {archiveDraft.after_days !== null && (
+              <>
+                <label className="toggle-item">
+                  <input
+                    type="checkbox"
+                    checked={archiveDraft.keep_pinned}
+                    onChange={(e) => editArchive({ keep_pinned: e.target.checked })}
+                  />
+                  <span>Keep pinned and starred notes</span>
+                </label>
+                <label className="toggle-item">
+                  <input
+                    type="checkbox"
+                    checked={archiveDraft.keep_tagged}
+                    onChange={(e) => editArchive({ keep_tagged: e.target.checked })}
+                  />
+                  <span>Keep tagged notes</span>
+                </label>
+              </>
+            )}
+            {archivePreview !== null && JSON.stringify(archiveDraft) !== JSON.stringify(archivePolicy) && (
+              <div className="toggle-item">
+                <span>
+                  {archivePreview} notes would be archived. Open tasks are never archived, archived notes stay searchable.
+                </span>
+                <button className="website-button" onClick={applyArchive}>Apply</button>
+              </div>
+            )}
+          </section>
+        )}
+
+        {/* Window Placement Section */}
+        <section className="settings-section">
+          <h2>Window Placement</h2>
+          {placementWindows.map(({ label, name }) => (
+            <label className="toggle-item" key={label}>
+              <span>{name}</span>
+              <select
+                value={placements[label] ?? ""}
+                onChange={(e) => changePlacement(label, e.target.value)}
+              >
+                <option value="snap_to_tray">Snap to tray</option>
+                <option value="remember_position">Remember position</option>
+                <option value="center">Center of active screen</option>
+                <option value="top_third">Top third of active screen</option>
+              </select>
+            </label>
+          ))}
+        </section>
+
+        {/* Reminders Section */}
+        <section className="settings-section">
+          <h2>Reminders</h2>
+          {reminders.map((reminder) => (
+            <label className="toggle-item" key={reminder.id}>
+              <input
+                type="checkbox"
+                checked={reminder.enabled}
+                onChange={(e) => changeReminder({ ...reminder, enabled: e.target.checked })}
+              />
+              <span>{REMINDER_NAMES[reminder.kind]}</span>
+              <input
+                type="time"
+                value={reminder.time_of_day ?? ""}
+                onChange={(e) => changeReminder({ ...reminder, time_of_day: e.target.value })}
+              />
+              <select
+                value={reminder.workdays_only ? "workdays" : "daily"}
+                onChange={(e) =>
+                  changeReminder({ ...reminder, workdays_only: e.target.value === "workdays" })
+                }
+              >
+                <option value="workdays">Workdays</option>
+                <option value="daily">Every day</option>
+              </select>
+            </label>
+          ))}
+          <div className="toggle-item workdays">
+            <span>Workdays</span>
+            {WEEKDAYS.map((day) => (
+              <label key={day}>
+                <input
+                  type="checkbox"
+                  checked={calendar.workdays.includes(day)}
+                  onChange={(e) => toggleWorkday(day, e.target.checked)}
+                />
+                {day}
+              </label>
+            ))}
+          </div>
+          <label className="toggle-item holidays">
+            <span>Holidays (one YYYY-MM-DD per line)</span>
+            <textarea
+              value={holidaysText}
+              onChange={(e) => setHolidaysText(e.target.value)}
+              onBlur={saveHolidays}
+              rows={3}
+            />
+          </label>
+        </section>
+
+        {/* Sprint Section */}
+        <section className="settings-section">
+          <h2>Sprint</h2>
+          <label className="toggle-item">
+            <span>Length</span>
+            <select
+              value={sprint.length_weeks}
+              onChange={(e) => saveSprint({ ...sprint, length_weeks: Number(e.target.value) })}
+            >
+              {[1, 2, 3, 4].map((weeks) => (
+                <option key={weeks} value={weeks}>
+                  {weeks} {weeks === 1 ? "week" : "weeks"}
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="toggle-item">
+            <span>Starts on</span>
+            <select
+              value={sprint.start_weekday}
+              onChange={(e) => saveSprint({ ...sprint, start_weekday: e.target.value })}
+            >
+              {WEEKDAYS.map((day) => (
+                <option key={day} value={day}>
+                  {day}
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="toggle-item">
+            <span>First day of a past sprint</span>
+            <input
+              type="date"
+              value={sprint.anchor ?? ""}
+              onChange={(e) => saveSprint({ ...sprint, anchor: e.target.value || null })}
+            />
+          </label>
+        </section>
+
         {/* Shortcuts Section */}
         <section className="settings-section">
           <h2>Keyboard Shortcuts</h2>
@@ -106,6 +701,18 @@ function Settings() {
               <span className="shortcut-key">⌘ + Shift + Space</span>
               <span className="shortcut-desc">Quick Capture (Global)</span>
             </div>
+            <div className="shortcut-item">
+              <span className="shortcut-key">⌘ + Alt + Z</span>
+              <span className="shortcut-desc">Undo Last Quick Capture (Global)</span>
+            </div>
+            <div className="shortcut-item">
+              <span className="shortcut-key">⌘ + Alt + V</span>
+              <span className="shortcut-desc">Capture Clipboard (Global)</span>
+            </div>
+            <div className="shortcut-item">
+              <span className="shortcut-key">⌘ + Z / ⌘ + Shift + Z</span>
+              <span className="shortcut-desc">Undo / Redo Note Changes</span>
+            </div>
             <div className="shortcut-item">
               <span className="shortcut-key">⌘ + S</span>
               <span className="shortcut-desc">Save Note (Manual)</span>
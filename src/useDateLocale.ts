@@ -0,0 +1,29 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+// BCP 47 tags for the app languages in i18n.rs
+const DATE_LOCALES: Record<string, string> = {
+  de: "de-DE",
+  en: "en-US",
+};
+
+// Locale dates are formatted in, following the language chosen in the
+// settings. Re-read on focus, since it is changed in the settings window.
+export function useDateLocale(): string {
+  const [dateLocale, setDateLocale] = useState(DATE_LOCALES.de);
+
+  useEffect(() => {
+    const load = () =>
+      invoke<string>("get_locale")
+        .then((locale) => setDateLocale(DATE_LOCALES[locale] ?? DATE_LOCALES.en))
+        .catch((error) => console.error("Failed to load locale:", error));
+    load();
+    const unlistenFocus = getCurrentWindow().listen("tauri://focus", load);
+    return () => {
+      unlistenFocus.then(f => f());
+    };
+  }, []);
+
+  return dateLocale;
+}